@@ -2,15 +2,23 @@ use {
 	libc::{c_char, c_double, c_longlong, c_void},
 	std::{
 		self,
+		collections::HashMap,
 		ffi::{CStr, CString},
+		path::Path,
 	},
 };
 
 use serde_json::Value;
 
+#[cfg(feature = "ndarray")]
+use ndarray::Array2;
+
+#[cfg(feature = "dataframe")]
+use polars::prelude::*;
+
 use lightgbm_sys;
 
-use crate::{Dataset, Error, Result, SingleRowPredictor};
+use crate::{Dataset, Error, Params, PredictConfig, Result, SingleRowPredictor};
 
 /// Core model in LightGBM, containing functions for training, evaluating and predicting.
 pub struct Booster {
@@ -18,6 +26,10 @@ pub struct Booster {
 	pub(crate) param_overrides: CString,
 	/// This is necessary because of https://github.com/microsoft/LightGBM/issues/6142
 	race_workaround_mutex: std::sync::Mutex<()>,
+	/// LightGBM keeps pointers to the datasets used for training and validation, so we need to
+	/// keep them alive as long as the booster
+	train_data: Option<Dataset>,
+	valid_data: Vec<Dataset>,
 }
 
 // LGBM_BoosterPredictForMat is always thread-safe
@@ -38,17 +50,20 @@ impl Booster {
 			handle,
 			param_overrides,
 			race_workaround_mutex: std::sync::Mutex::new(()),
+			train_data: None,
+			valid_data: Vec::new(),
 		}
 	}
 
 	/// Init from model file.
-	pub fn from_file_with_param_overrides(filename: &str, param_overrides: &str) -> Result<Self> {
-		let filename_str = CString::new(filename)
-			.map_err(|e| Error::new(format!("Failed to create cstring: {e}")))?;
+	pub fn from_file_with_param_overrides(
+		filename: impl AsRef<Path>,
+		param_overrides: &str,
+	) -> Result<Self> {
+		let filename_str = crate::path_to_cstring(filename.as_ref())?;
 
-		let param_overrides = CString::new(param_overrides).map_err(|e| {
-			Error::new(format!("Failed to convert param_overrides to CString: {e}"))
-		})?;
+		let param_overrides = CString::new(param_overrides)
+			.map_err(|e| Error::from_other("failed to convert param_overrides to cstring", e))?;
 
 		let mut out_num_iterations = 0;
 		let mut handle = std::ptr::null_mut();
@@ -63,17 +78,16 @@ impl Booster {
 		Ok(Booster::new(handle, param_overrides))
 	}
 
-	pub fn from_file(filename: &str) -> Result<Self> {
+	pub fn from_file(filename: impl AsRef<Path>) -> Result<Self> {
 		Self::from_file_with_param_overrides(filename, "")
 	}
 
 	pub fn from_string_with_param_overrides(string: &[u8], param_overrides: &str) -> Result<Self> {
-		let model_cstring = CString::new(string)
-			.map_err(|e| Error::new(format!("Failed to create cstring: {e}")))?;
+		let model_cstring =
+			CString::new(string).map_err(|e| Error::from_other("failed to make cstring", e))?;
 
-		let param_overrides = CString::new(param_overrides).map_err(|e| {
-			Error::new(format!("Failed to convert param_overrides to CString: {e}"))
-		})?;
+		let param_overrides = CString::new(param_overrides)
+			.map_err(|e| Error::from_other("failed to convert param_overrides to cstring", e))?;
 
 		let mut out_num_iterations = 0;
 		let mut handle = std::ptr::null_mut();
@@ -126,29 +140,99 @@ impl Booster {
 	/// let bst = Booster::train(dataset, &params).unwrap();
 	/// ```
 	pub fn train(dataset: Dataset, parameter: &Value) -> Result<Self> {
+		Self::train_with_params(dataset, &Params::try_from(parameter)?)
+	}
+
+	/// Create a new Booster model with given Dataset and [`Params`], training it for
+	/// `num_iterations` iterations, like [`TrainConfig::run`](crate::TrainConfig::run).
+	///
+	/// Parameters are [validated](Params::validate) before training starts.
+	pub fn train_with_params(dataset: Dataset, params: &Params) -> Result<Self> {
 		// get num_iterations
-		let num_iterations: i64 = if parameter["num_iterations"].is_null() {
-			100
-		} else {
-			parameter["num_iterations"]
-				.as_i64()
-				.ok_or_else(|| Error::new("failed to unwrap num_iterations"))?
-		};
+		let num_iterations = params.num_iterations()?;
 
-		// exchange params {"x": "y", "z": 1} => "x=y z=1"
-		let mut params_string = String::new();
-		for (k, v) in parameter
-			.as_object()
-			.ok_or_else(|| Error::new("failed to convert param to object"))?
-			.iter()
-		{
-			if !params_string.is_empty() {
-				params_string.push(' ');
+		let mut booster = Self::create(dataset, params)?;
+		for _ in 0..num_iterations {
+			if booster.update_one_iter()? {
+				break;
+			}
+		}
+
+		Ok(booster)
+	}
+
+	/// Load the model in `init_model` and continue boosting it for `additional_iterations`
+	/// rounds on `dataset`.
+	///
+	/// The dataset must have the same number of features as the saved model, and its
+	/// [init score](Dataset::set_init_score) must be set to the saved model's
+	/// [raw predictions](PredictType::RawScore) on that same data, so that new trees fit
+	/// what the saved model got wrong. The returned booster contains the trees of the
+	/// saved model followed by the new ones.
+	///
+	/// `num_iterations` in `params` is ignored.
+	pub fn continue_from_file(
+		init_model: impl AsRef<Path>,
+		dataset: Dataset,
+		params: &Params,
+		additional_iterations: i32,
+	) -> Result<Self> {
+		if additional_iterations < 0 {
+			return Err(Error::invalid_input(format!(
+				"additional_iterations ({additional_iterations}) must not be negative"
+			)));
+		}
+		let init_model = init_model.as_ref();
+		let init_booster = Self::from_file(init_model)?;
+		let init_num_feature = usize::try_from(init_booster.num_feature()?)
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		let n_features = dataset.n_features()?;
+		if init_num_feature != n_features {
+			return Err(Error::invalid_input(format!(
+				"dataset has {n_features} features but the model in {} has {init_num_feature}",
+				init_model.display()
+			)));
+		}
+		if dataset.get_init_score()?.is_none() {
+			return Err(Error::invalid_input(
+				"dataset needs the init model's raw scores as init score to continue training",
+			));
+		}
+
+		let mut booster = Self::create(dataset, params)?;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterMerge(
+			booster.handle,
+			init_booster.handle
+		))?;
+		for _ in 0..additional_iterations {
+			booster.update_one_iter()?;
+		}
+
+		Ok(booster)
+	}
+
+	/// Create a Booster for training on `dataset`, without training any iteration yet.
+	pub(crate) fn create(dataset: Dataset, params: &Params) -> Result<Self> {
+		params.validate()?;
+
+		// LightGBM fails deep into training with a confusing message on invalid class labels
+		if let Some(num_class) = params.multiclass_num_class()? {
+			let num_class = num_class as f32;
+			let is_class_index =
+				|label: f32| label >= 0. && label < num_class && label.fract() == 0.;
+			if let Some((row, label)) = dataset
+				.get_label()?
+				.into_iter()
+				.enumerate()
+				.find(|&(_, label)| !is_class_index(label))
+			{
+				return Err(Error::invalid_input(format!(
+					"label {label} at row {row} is not a class index in 0..{num_class}"
+				)));
 			}
-			params_string.push_str(&format!("{k}={v}"));
 		}
-		let params_cstring = CString::new(params_string)
-			.map_err(|e| Error::from_other("failed to make cstring", e))?;
+
+		let params_cstring = params.to_cstring()?;
 
 		let mut handle = std::ptr::null_mut();
 		lgbm_call!(lightgbm_sys::LGBM_BoosterCreate(
@@ -158,20 +242,78 @@ impl Booster {
 		))?;
 		// It is very important to create the booster immediately after a successful call to avoid
 		// memory leak on subsequent error (as we rely on the drop impl of Booster to be called)
-		let booster = Booster::new(
+		let mut booster = Booster::new(
 			handle,
-			CString::new("").map_err(|e| Error::new(format!("Failed to allocate CString: {e}")))?,
+			CString::new("").map_err(|e| Error::from_other("failed to make cstring", e))?,
 		);
+		booster.train_data = Some(dataset);
+
+		Ok(booster)
+	}
 
+	/// Train one more boosting iteration on the training data, returns whether training can't
+	/// go any further (e.g. because no split improves the model).
+	///
+	/// This is only possible on a booster that was trained from a dataset, not on one loaded
+	/// from a model file.
+	pub fn update_one_iter(&mut self) -> Result<bool> {
+		if self.train_data.is_none() {
+			return Err(Error::invalid_input(
+				"only a booster trained from a dataset can keep training",
+			));
+		}
 		let mut is_finished: i32 = 0;
-		for _ in 1..num_iterations {
-			lgbm_call!(lightgbm_sys::LGBM_BoosterUpdateOneIter(
-				handle,
-				&mut is_finished
-			))?;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterUpdateOneIter(
+			self.handle,
+			&mut is_finished
+		))?;
+		Ok(is_finished != 0)
+	}
+
+	/// Replace the training data, returning the previous one. Further iterations (see
+	/// [`update_one_iter`](Self::update_one_iter)) then fit the current model to `dataset`.
+	///
+	/// `dataset` must be binned like the current training data, e.g. created with it as
+	/// reference (see [`Dataset::from_mat_with_reference`]). Its fields are used as set when
+	/// calling this, so this allows relabeling the data without binning it again:
+	/// ```
+	/// use lightgbm::{Booster, Dataset, Params};
+	///
+	/// let data = [0.1, 0.2, 0.3, 0.7, 0.8, 0.9];
+	/// let dataset = Dataset::from_mat(&data, 6, &[0., 0., 0., 1., 1., 1.]).unwrap();
+	/// let next_dataset = Dataset::from_mat_with_reference(&data, 6, &[0.; 6], &dataset).unwrap();
+	/// let params = Params::new().set("objective", "binary").set("min_data_in_leaf", 1);
+	/// let mut bst = Booster::train_with_params(dataset, &params).unwrap();
+	///
+	/// let mut dataset = bst.reset_training_data(next_dataset).unwrap();
+	/// dataset.set_label(&[1., 1., 1., 0., 0., 0.]).unwrap();
+	/// bst.reset_training_data(dataset).unwrap();
+	/// bst.update_one_iter().unwrap();
+	/// ```
+	///
+	/// Validation data added with [`add_valid_data`](Self::add_valid_data) is kept.
+	pub fn reset_training_data(&mut self, dataset: Dataset) -> Result<Dataset> {
+		let previous = self.train_data.take().ok_or_else(|| {
+			Error::invalid_input(
+				"only a booster trained from a dataset can have its training data reset",
+			)
+		})?;
+		if let Err(e) = lgbm_call!(lightgbm_sys::LGBM_BoosterResetTrainingData(
+			self.handle,
+			dataset.handle
+		)) {
+			self.train_data = Some(previous);
+			return Err(e);
 		}
+		// LightGBM keeps a pointer to the dataset, so it has to live as long as the booster
+		self.train_data = Some(dataset);
+		Ok(previous)
+	}
 
-		Ok(booster)
+	/// Remove the trees of the last iteration.
+	pub(crate) fn rollback_one_iter(&mut self) -> Result<()> {
+		lgbm_call!(lightgbm_sys::LGBM_BoosterRollbackOneIter(self.handle))?;
+		Ok(())
 	}
 
 	/// Predict results for given data.
@@ -200,23 +342,228 @@ impl Booster {
 		if data.is_empty() {
 			return Ok(Vec::new());
 		}
-		let num_feature: i32 = self.num_feature()?;
-		let n_features: usize = num_feature
+		let n_features: usize = self
+			.num_feature()?
 			.try_into()
-			.map_err(|_| Error::new("number of features doesn't fit into an usize"))?;
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
 		if data.len() % n_features != 0 {
-			return Err(Error::new(format!(
+			return Err(Error::invalid_input(format!(
 				"data len is not a multiple of n_features ({n_features}), \
 					but all rows should have the same length",
 			)));
 		}
-		let n_rows = data.len() / n_features;
+		self.predict_with_type(data, data.len() / n_features, PredictType::Normal)
+	}
+
+	/// Predict results of the given type for `n_rows` rows of data in row-major order.
+	///
+	/// The output has a fixed number of values per row, so
+	/// `output.chunks(output.len() / n_rows)` gives the output for each row. That number
+	/// depends on `predict_type`, see [`PredictType`].
+	///
+	/// The output is row-major: for multiclass models the values are
+	/// `[row0_class0, row0_class1, ..., row1_class0, ...]`, never grouped by class.
+	/// [`predict_classes`](Self::predict_classes) splits it into rows.
+	///
+	/// Unlike [`predict`](Self::predict) this only takes `&self`, working around the data race
+	/// in LightGBM ([#6142](https://github.com/microsoft/LightGBM/issues/6142)) by locking.
+	pub fn predict_with_type(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		predict_type: PredictType,
+	) -> Result<Vec<f64>> {
+		self.predict_with_parameters(data, n_rows, true, predict_type, &self.param_overrides)
+	}
+
+	/// Predict results for `n_rows` rows of data in row-major order, as configured by
+	/// `config`, e.g. with [early stopping](PredictConfig::early_stopping).
+	///
+	/// The output is laid out like that of [`predict_with_type`](Self::predict_with_type).
+	pub fn predict_with_config(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		config: &PredictConfig,
+	) -> Result<Vec<f64>> {
+		let parameters = config.parameters(&self.param_overrides.to_string_lossy())?;
+		let parameters =
+			CString::new(parameters).map_err(|e| Error::from_other("failed to make cstring", e))?;
+		self.predict_with_parameters(data, n_rows, true, config.predict_type, &parameters)
+	}
+
+	/// Predict results of the given type for `n_rows` rows of data in row-major order, writing
+	/// them into `out` instead of allocating.
+	///
+	/// `out` must have exactly the length of the output of
+	/// [`predict_with_type`](Self::predict_with_type), which is laid out the same way.
+	pub fn predict_into(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		predict_type: PredictType,
+		out: &mut [f64],
+	) -> Result<()> {
+		self.predict_into_with_parameters(
+			data,
+			n_rows,
+			true,
+			predict_type,
+			&self.param_overrides,
+			out,
+		)
+	}
+
+	/// Predict results for `n_rows` rows of data, writing them to `writer` as they are
+	/// computed rather than collecting them, e.g. to stream them to stdout or a socket.
+	///
+	/// Rows are predicted by chunks of 4096 rows, so memory use doesn't grow with `n_rows`.
+	/// Each row is written on its own line, its values separated by tabs, formatted so that
+	/// they parse back to the same `f64`. Rows without values, e.g. leaf indices of a model
+	/// without trees, are empty lines. The writer isn't flushed.
+	pub fn predict_to_writer<W: std::io::Write>(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		predict_type: PredictType,
+		writer: &mut W,
+	) -> Result<()> {
+		let n_features: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		check_data_len(data, n_rows, n_features)?;
+		let write_error = |e| Error::io(format!("failed to write predictions: {e}"));
+		for chunk in data.chunks(PREDICT_TO_WRITER_CHUNK_ROWS * n_features.max(1)) {
+			let chunk_rows = chunk.len() / n_features.max(1);
+			let output = self.predict_with_type(chunk, chunk_rows, predict_type)?;
+			let row_len = output.len() / chunk_rows;
+			for row_idx in 0..chunk_rows {
+				let row = &output[row_idx * row_len..(row_idx + 1) * row_len];
+				for (i, value) in row.iter().enumerate() {
+					let separator = if i == 0 { "" } else { "\t" };
+					write!(writer, "{separator}{value}").map_err(write_error)?;
+				}
+				writeln!(writer).map_err(write_error)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Predict results from a memory-mapped feature matrix of `n_rows` rows in row-major
+	/// order, writing them into `out`, like [`predict_into`](Self::predict_into).
+	///
+	/// LightGBM reads `mmap` in place, so the features are never copied, which is what makes
+	/// scoring matrices larger than memory possible. The mapping may be read-only: it is
+	/// never written to. As for any `&[f64]`, it must be aligned for `f64`, which is the case
+	/// when the `f64`s start at a page boundary or at a multiple of 8 bytes in the file, and
+	/// the file must not be modified while the slice is borrowed.
+	pub fn predict_mmap(
+		&self,
+		mmap: &[f64],
+		n_rows: usize,
+		predict_type: PredictType,
+		out: &mut [f64],
+	) -> Result<()> {
+		self.predict_into(mmap, n_rows, predict_type, out)
+	}
+
+	/// Predict results of the given type for data given as columns: `columns[j]` holds the
+	/// values of feature `j` for every row.
+	///
+	/// All columns must have the same length, which is the number of rows. This avoids
+	/// transposing struct-of-arrays data into rows: the columns are concatenated and passed
+	/// to LightGBM in column-major order. The output is laid out like that of
+	/// [`predict_with_type`](Self::predict_with_type), row by row.
+	pub fn predict_colmajor(
+		&self,
+		columns: &[&[f64]],
+		predict_type: PredictType,
+	) -> Result<Vec<f64>> {
+		let n_rows = columns.first().map_or(0, |column| column.len());
+		if let Some(j) = columns.iter().position(|column| column.len() != n_rows) {
+			return Err(Error::invalid_input(format!(
+				"column {j} has {} values, but column 0 has {n_rows}",
+				columns[j].len()
+			)));
+		}
+		self.predict_with_parameters(
+			&columns.concat(),
+			n_rows,
+			false,
+			predict_type,
+			&self.param_overrides,
+		)
+	}
+
+	fn predict_with_parameters(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		is_row_major: bool,
+		predict_type: PredictType,
+		parameters: &CStr,
+	) -> Result<Vec<f64>> {
+		if n_rows == 0 {
+			// checks the data length
+			self.predict_into_with_parameters(
+				data,
+				n_rows,
+				is_row_major,
+				predict_type,
+				parameters,
+				&mut [],
+			)?;
+			return Ok(Vec::new());
+		}
 		let nrow = n_rows
 			.try_into()
-			.map_err(|_| Error::new("number of rows doesn't fit into an i32"))?;
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		let mut out_result = vec![0_f64; self.predict_output_len(nrow, predict_type)?];
+		self.predict_into_with_parameters(
+			data,
+			n_rows,
+			is_row_major,
+			predict_type,
+			parameters,
+			&mut out_result,
+		)?;
+		Ok(out_result)
+	}
 
-		let predict_output_len = self.predict_output_len(nrow)?;
-		let out_result: Vec<f64> = vec![Default::default(); predict_output_len];
+	fn predict_into_with_parameters(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		is_row_major: bool,
+		predict_type: PredictType,
+		parameters: &CStr,
+		out_result: &mut [f64],
+	) -> Result<()> {
+		let num_feature: i32 = self.num_feature()?;
+		let n_features: usize = num_feature
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		check_data_len(data, n_rows, n_features)?;
+		let nrow = n_rows
+			.try_into()
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		let predict_output_len = match n_rows {
+			0 => 0,
+			_ => self.predict_output_len(nrow, predict_type)?,
+		};
+		if out_result.len() != predict_output_len {
+			return Err(Error::invalid_input(format!(
+				"output buffer has length {} but the prediction has {predict_output_len} values",
+				out_result.len()
+			)));
+		}
+		if n_rows == 0 {
+			return Ok(());
+		}
+
+		// https://github.com/microsoft/LightGBM/issues/6142
+		let _guard = self.race_workaround_mutex.lock().unwrap();
 
 		let mut out_length: c_longlong = 0;
 		lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMat(
@@ -224,14 +571,14 @@ impl Booster {
 			data.as_ptr() as *const c_void,
 			lightgbm_sys::C_API_DTYPE_FLOAT64,
 			nrow,
-			num_feature,                        // ncol
-			1_i32,                              // is_row_major
-			lightgbm_sys::C_API_PREDICT_NORMAL, // predict_type
-			0_i32,                              // start_iteration
-			-1_i32,                             // num_iteration
-			self.param_overrides.as_ptr() as *const c_char,
+			num_feature, // ncol
+			i32::from(is_row_major),
+			predict_type.as_c_api(),
+			0_i32,  // start_iteration
+			-1_i32, // num_iteration
+			parameters.as_ptr() as *const c_char,
 			&mut out_length,
-			out_result.as_ptr() as *mut c_double
+			out_result.as_mut_ptr() as *mut c_double
 		))?;
 
 		assert!(
@@ -239,23 +586,471 @@ impl Booster {
 			"Unexpected written output length"
 		);
 
-		Ok(out_result)
+		Ok(())
+	}
+
+	/// Predict results for `n_rows` rows of data like [`predict_with_type`](Self::predict_with_type)
+	/// with [`PredictType::Normal`], and check that every predicted value is finite.
+	///
+	/// A broken model or extreme inputs may produce NaN or infinite predictions, which this
+	/// reports as an error naming the first offending row, instead of letting them silently
+	/// propagate. The check costs a pass over the output.
+	pub fn predict_checked(&self, data: &[f64], n_rows: usize) -> Result<Vec<f64>> {
+		let output = self.predict_with_type(data, n_rows, PredictType::Normal)?;
+		if let Some(idx) = output.iter().position(|value| !value.is_finite()) {
+			let row = idx / (output.len() / n_rows);
+			return Err(Error::ffi(format!(
+				"prediction for row {row} is not finite: {}",
+				output[idx]
+			)));
+		}
+		Ok(output)
+	}
+
+	/// Compare the predictions of this model and of `other` on `n_rows` rows of data, e.g. to
+	/// check a retrained model didn't drift from the previous one.
+	///
+	/// Both models should have the same number of features and classes. Differences are
+	/// computed on [`Normal`](PredictType::Normal) predictions, i.e. on probabilities for
+	/// classification models.
+	pub fn prediction_diff(
+		&self,
+		other: &Booster,
+		data: &[f64],
+		n_rows: usize,
+	) -> Result<PredictionDiff> {
+		let (num_feature, other_num_feature) = (self.num_feature()?, other.num_feature()?);
+		if num_feature != other_num_feature {
+			return Err(Error::invalid_input(format!(
+				"can't compare a model with {num_feature} features to a model with \
+					{other_num_feature} features"
+			)));
+		}
+		let (num_classes, other_num_classes) = (self.num_classes()?, other.num_classes()?);
+		if num_classes != other_num_classes {
+			return Err(Error::invalid_input(format!(
+				"can't compare a model with {num_classes} classes to a model with \
+					{other_num_classes} classes"
+			)));
+		}
+		let predictions = self.predict_with_type(data, n_rows, PredictType::Normal)?;
+		let other_predictions = other.predict_with_type(data, n_rows, PredictType::Normal)?;
+		let mut diff = PredictionDiff::default();
+		let mut sum_abs = 0.;
+		for (prediction, other_prediction) in predictions.iter().zip(&other_predictions) {
+			let abs = (prediction - other_prediction).abs();
+			diff.max_abs = diff.max_abs.max(abs);
+			sum_abs += abs;
+			if prediction != other_prediction {
+				diff.num_changed += 1;
+			}
+		}
+		if !predictions.is_empty() {
+			diff.mean_abs = sum_abs / predictions.len() as f64;
+		}
+		Ok(diff)
+	}
+
+	/// Predict both raw scores and probabilities for `n_rows` rows of data, predicting only
+	/// once.
+	///
+	/// Probabilities are derived from the raw scores in Rust, with the link function of the
+	/// model's [objective](Self::objective):
+	/// - `binary`: sigmoid, `1 / (1 + exp(-sigmoid * raw))`, with the `sigmoid` parameter of
+	///   the model
+	/// - `multiclass`: softmax over the raw scores of the classes of each row
+	/// - `multiclassova`: sigmoid of each raw score, as for `binary`
+	/// - `cross_entropy`: sigmoid, `1 / (1 + exp(-raw))`
+	///
+	/// Other objectives don't predict probabilities, and are reported as errors.
+	pub fn predict_raw_and_proba(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+	) -> Result<(Vec<f64>, Vec<f64>)> {
+		let objective = self.objective()?;
+		let mut tokens = objective.split_whitespace();
+		let name = tokens.next().unwrap_or_default();
+		let sigmoid = tokens
+			.find_map(|token| token.strip_prefix("sigmoid:"))
+			.map(|sigmoid| {
+				sigmoid
+					.parse::<f64>()
+					.map_err(|e| Error::from_other("failed to parse sigmoid parameter", e))
+			})
+			.transpose()?
+			.unwrap_or(1.);
+		let raw = self.predict_with_type(data, n_rows, PredictType::RawScore)?;
+		let proba = match name {
+			"binary" | "multiclassova" | "cross_entropy" => raw
+				.iter()
+				.map(|&raw| 1. / (1. + (-sigmoid * raw).exp()))
+				.collect(),
+			"multiclass" => {
+				let mut proba = Vec::with_capacity(raw.len());
+				if let Some(row_len) = raw.len().checked_div(n_rows) {
+					for row in raw.chunks(row_len) {
+						let max = row.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+						let exps = row.iter().map(|&raw| (raw - max).exp());
+						let sum: f64 = exps.clone().sum();
+						proba.extend(exps.map(|exp| exp / sum));
+					}
+				}
+				proba
+			}
+			_ => {
+				return Err(Error::invalid_input(format!(
+					"objective {objective} doesn't predict probabilities"
+				)))
+			}
+		};
+		Ok((raw, proba))
+	}
+
+	/// Predict the probability of each class for `n_rows` rows of data, as one `Vec` of
+	/// [`num_classes`](Self::num_classes) values per row.
+	///
+	/// `output[row][class]` is the value for `class` at `row`, taken from the row-major flat
+	/// output of [`predict_with_type`](Self::predict_with_type). Regression and binary
+	/// classification models have a single class.
+	pub fn predict_classes(&self, data: &[f64], n_rows: usize) -> Result<Vec<Vec<f64>>> {
+		let num_class: usize = self
+			.num_classes()?
+			.try_into()
+			.map_err(|_| Error::ffi("negative number of classes returned by LGBM C API"))?;
+		let output = self.predict_with_type(data, n_rows, PredictType::Normal)?;
+		debug_assert_eq!(
+			output.len(),
+			n_rows * num_class,
+			"predictions should have one value per row and class"
+		);
+		if num_class == 0 {
+			return Ok(Vec::new());
+		}
+		Ok(output.chunks(num_class).map(<[f64]>::to_vec).collect())
+	}
+
+	/// Predict the most likely class of each of the `n_rows` rows of data, with its
+	/// probability.
+	///
+	/// For multiclass models this is the class with the highest predicted probability. Binary
+	/// models are handled as having two classes: the class is `1` if the predicted
+	/// probability is at least `0.5`, and `0` otherwise with probability `1 - p`. Models that
+	/// aren't classifiers are reported as errors.
+	pub fn predict_top_class(&self, data: &[f64], n_rows: usize) -> Result<Vec<ClassPrediction>> {
+		let objective = self.objective()?;
+		let is_binary = match objective.split_whitespace().next().unwrap_or_default() {
+			"binary" | "cross_entropy" => true,
+			"multiclass" | "multiclassova" => false,
+			_ => {
+				return Err(Error::invalid_input(format!(
+					"objective {objective} is not a classification objective"
+				)))
+			}
+		};
+		let output = self.predict_with_type(data, n_rows, PredictType::Normal)?;
+		if is_binary {
+			return Ok(output
+				.into_iter()
+				.map(|p| {
+					if p >= 0.5 {
+						ClassPrediction {
+							class: 1,
+							probability: p,
+						}
+					} else {
+						ClassPrediction {
+							class: 0,
+							probability: 1. - p,
+						}
+					}
+				})
+				.collect());
+		}
+		let Some(num_class) = output.len().checked_div(n_rows) else {
+			return Ok(Vec::new());
+		};
+		debug_assert_eq!(
+			Ok(num_class),
+			self.num_classes().map(|n| n as usize),
+			"predictions should have one value per row and class"
+		);
+		Ok(output
+			.chunks(num_class)
+			.map(|probabilities| {
+				let mut best = (0, f64::NEG_INFINITY);
+				for (class, &probability) in probabilities.iter().enumerate() {
+					if probability > best.1 {
+						best = (class, probability);
+					}
+				}
+				let (class, probability) = best;
+				ClassPrediction { class, probability }
+			})
+			.collect())
+	}
+
+	/// Predict results of regression models for `n_rows` rows of data, on the scale of the
+	/// response.
+	///
+	/// Predictions are derived from the raw scores in Rust, with the inverse link function of
+	/// the model's [objective](Self::objective):
+	/// - `regression`, `regression_l1`, `huber`, `fair`, `quantile` and `mape`: identity
+	///   (or `sign(raw) * raw^2` if the model was trained with `reg_sqrt`)
+	/// - `poisson`, `tweedie` and `gamma`: `exp`, as their raw scores are on the log scale
+	///
+	/// Other objectives are reported as errors.
+	pub fn predict_response(&self, data: &[f64], n_rows: usize) -> Result<Vec<f64>> {
+		let objective = self.objective()?;
+		let mut tokens = objective.split_whitespace();
+		let inverse_link: fn(f64) -> f64 = match tokens.next().unwrap_or_default() {
+			"regression" | "regression_l1" | "huber" | "fair" | "quantile" | "mape" => {
+				if tokens.any(|token| token == "sqrt") {
+					|raw| raw.signum() * raw * raw
+				} else {
+					|raw| raw
+				}
+			}
+			"poisson" | "tweedie" | "gamma" => f64::exp,
+			_ => {
+				return Err(Error::invalid_input(format!(
+					"objective {objective} is not a regression objective"
+				)))
+			}
+		};
+		let mut output = self.predict_with_type(data, n_rows, PredictType::RawScore)?;
+		output
+			.iter_mut()
+			.for_each(|value| *value = inverse_link(*value));
+		Ok(output)
+	}
+
+	/// Run a throwaway prediction on a row of zeros, so that LightGBM's lazy initialization
+	/// happens now rather than during the first actual prediction.
+	///
+	/// This is optional, and only useful when the latency of the first prediction matters,
+	/// e.g. when serving. `n_features` must be the number of features of the model.
+	pub fn warmup(&self, n_features: usize) -> Result<()> {
+		self.predict_with_type(&vec![0.; n_features], 1, PredictType::Normal)?;
+		Ok(())
+	}
+
+	/// Predict results for `n_rows` rows of data as if the features at the indices
+	/// `masked_features` were missing, e.g. for feature ablation studies.
+	///
+	/// The data is copied with the masked columns set to NaN, which LightGBM handles as a
+	/// missing value. Note that for features that had no missing value in the training data,
+	/// LightGBM treats NaN as 0 (see
+	/// [`use_missing`](https://lightgbm.readthedocs.io/en/latest/Parameters.html#use_missing)).
+	pub fn predict_masked(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		masked_features: &[usize],
+	) -> Result<Vec<f64>> {
+		let n_features: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		if let Some(&feature) = masked_features.iter().find(|&&j| j >= n_features) {
+			return Err(Error::invalid_input(format!(
+				"can't mask feature {feature}, the model has {n_features} features"
+			)));
+		}
+		check_data_len(data, n_rows, n_features)?;
+		let mut masked = data.to_vec();
+		for row in 0..n_rows {
+			for &j in masked_features {
+				masked[row * n_features + j] = f64::NAN;
+			}
+		}
+		self.predict_with_type(&masked, n_rows, PredictType::Normal)
+	}
+
+	/// Predict results for `n_rows` rows of data, after clamping each feature `j` to
+	/// `[min[j], max[j]]`.
+	///
+	/// This guards against out-of-distribution extremes in serving data. `min` and `max`
+	/// must have one bound per feature. NaN feature values are left as is, so they are still
+	/// handled as missing values.
+	pub fn predict_clamped(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		min: &[f64],
+		max: &[f64],
+	) -> Result<Vec<f64>> {
+		let n_features: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		if min.len() != n_features || max.len() != n_features {
+			return Err(Error::invalid_input(format!(
+				"min len ({}) and max len ({}) should both be n_features ({n_features})",
+				min.len(),
+				max.len(),
+			)));
+		}
+		if let Some(j) =
+			(0..n_features).find(|&j| min[j].is_nan() || max[j].is_nan() || min[j] > max[j])
+		{
+			return Err(Error::invalid_input(format!(
+				"bounds of feature {j} are not ordered: min is {}, max is {}",
+				min[j], max[j],
+			)));
+		}
+		let clamped: Vec<f64> = data
+			.iter()
+			.zip(min.iter().zip(max).cycle())
+			.map(|(&value, (&min, &max))| value.clamp(min, max))
+			.collect();
+		// `clamped` has the same length as `data`, so shape errors are reported by predict_with_type
+		self.predict_with_type(&clamped, n_rows, PredictType::Normal)
+	}
+
+	/// Predict results for `n_rows` rows of data, as an array of shape `(n_rows, num_class)`.
+	///
+	/// Regression and binary classification models have a single class, so the output shape
+	/// is `(n_rows, 1)`.
+	///
+	/// Note: the feature ```ndarray``` is required for this method
+	#[cfg(feature = "ndarray")]
+	pub fn predict_to_array2(&self, data: &[f64], n_rows: usize) -> Result<Array2<f64>> {
+		let n_classes = self.predict_output_len(1, PredictType::Normal)?;
+		let output = self.predict_with_type(data, n_rows, PredictType::Normal)?;
+		Array2::from_shape_vec((n_rows, n_classes), output)
+			.map_err(|e| Error::from_other("failed to reshape predictions", e))
+	}
+
+	/// Predict results of the given type for the rows of a polars DataFrame.
+	///
+	/// Columns are looked up by the model's [feature names](Self::feature_names), so they
+	/// can be in any order, and columns the model doesn't use are ignored. Null values are
+	/// treated as missing values.
+	///
+	/// Note: the feature ```dataframe``` is required for this method
+	#[cfg(feature = "dataframe")]
+	pub fn predict_dataframe(
+		&self,
+		dataframe: &DataFrame,
+		predict_type: PredictType,
+	) -> Result<Vec<f64>> {
+		let feature_names = self.feature_names()?;
+		let n_features = feature_names.len();
+		let (n_rows, _) = dataframe.shape();
+
+		let mut data = vec![0_f64; checked_len(n_rows, n_features)?];
+		for (col_idx, name) in feature_names.iter().enumerate() {
+			let series = dataframe.column(name).map_err(|_| {
+				Error::invalid_input(format!(
+					"column '{name}' used by the model is missing from the dataframe"
+				))
+			})?;
+			let series = series.cast::<Float64Type>()?;
+			let ca = series.unpack::<Float64Type>()?;
+			ca.into_iter().enumerate().for_each(|(row_idx, val)| {
+				data[row_idx * n_features + col_idx] = val.unwrap_or(f64::NAN)
+			});
+		}
+		self.predict_with_type(&data, n_rows, predict_type)
+	}
+
+	/// Predict feature contributions (SHAP values) for `n_rows` rows of data.
+	///
+	/// Returns one [`ContribRow`] per row, or for multiclass models one per row and class, in
+	/// row-major order (`[row0_class0, row0_class1, ..., row1_class0, ...]`). The
+	/// contributions of a row and its base sum up to its raw score prediction.
+	///
+	/// This is the way to get contributions. The flat output of
+	/// [`predict_with_type`](Self::predict_with_type) with [`PredictType::Contrib`] has the
+	/// same values, with the base as the last value of each row and class, which is easy to
+	/// mistake for a feature.
+	pub fn predict_contrib(&self, data: &[f64], n_rows: usize) -> Result<Vec<ContribRow>> {
+		let n_features: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		let contrib = self.predict_with_type(data, n_rows, PredictType::Contrib)?;
+		debug_assert_eq!(
+			contrib.len() % (n_features + 1),
+			0,
+			"contributions should have n_features + 1 values per row and class"
+		);
+		Ok(contrib
+			.chunks_exact(n_features + 1)
+			.map(|row| {
+				let (&base, contributions) = row.split_last().expect("row len is at least 1");
+				ContribRow {
+					contributions: contributions.to_vec(),
+					base,
+				}
+			})
+			.collect())
+	}
+
+	/// Predict the index of the leaf each row falls into, in each tree.
+	///
+	/// Returns one vector per row, with one leaf index per tree in the order of the trees in
+	/// the model, i.e. [`total_num_trees`](Self::total_num_trees) values. For multiclass
+	/// models trees are interleaved by class: the first `num_class` values are the first
+	/// iteration's trees for class 0, 1..., followed by the next iteration's.
+	pub fn predict_leaf_indices(&self, data: &[f64], n_rows: usize) -> Result<Vec<Vec<i32>>> {
+		let leaf_indices = self.predict_with_type(data, n_rows, PredictType::LeafIndex)?;
+		if leaf_indices.is_empty() {
+			// no rows, or no trees
+			return Ok(vec![Vec::new(); n_rows]);
+		}
+		Ok(leaf_indices
+			.chunks_exact(leaf_indices.len() / n_rows)
+			.map(|row| row.iter().map(|&leaf_index| leaf_index as i32).collect())
+			.collect())
+	}
+
+	/// Predict SHAP values for `n_rows` rows of data, split by row.
+	///
+	/// Returns, per row, the contribution of each feature, along with (in the second vector)
+	/// the bias of each row, i.e. the expected value of the model. The sum of a row's
+	/// contributions and its bias is the raw score prediction for that row.
+	///
+	/// This only supports models with a single output per row (regression, binary
+	/// classification...). [`predict_contrib`](Self::predict_contrib) also supports multiclass
+	/// models.
+	pub fn shap_values(&self, data: &[f64], n_rows: usize) -> Result<(Vec<Vec<f64>>, Vec<f64>)> {
+		let contrib = self.predict_with_type(data, n_rows, PredictType::Contrib)?;
+		let n_features: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		let row_len = n_features + 1;
+		if contrib.len() != checked_len(n_rows, row_len)? {
+			return Err(Error::invalid_input(
+				"shap_values only supports models with a single output per row, \
+					use predict_contrib instead",
+			));
+		}
+		Ok(contrib
+			.chunks_exact(row_len)
+			.map(|row| {
+				let (bias, contributions) = row.split_last().expect("row_len is at least 1");
+				(contributions.to_vec(), *bias)
+			})
+			.unzip())
 	}
 
 	pub fn predict_single_row(&self, data: &[f64]) -> Result<Vec<f64>> {
 		let num_feature: i32 = self.num_feature()?;
 		let n_features: usize = num_feature
 			.try_into()
-			.map_err(|_| Error::new("number of features doesn't fit into an usize"))?;
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
 		if data.len() != n_features {
-			return Err(Error::new(format!(
+			return Err(Error::invalid_input(format!(
 				"data len ({}) is equal to n_features ({n_features}), \
 					but this is a single-row prediction",
 				data.len(),
 			)));
 		}
 
-		let predict_output_len = self.predict_output_len(1)?;
+		let predict_output_len = self.predict_output_len(1, PredictType::Normal)?;
 		let out_result: Vec<f64> = vec![Default::default(); predict_output_len];
 
 		let mut out_length: c_longlong = 0;
@@ -263,7 +1058,7 @@ impl Booster {
 			self.handle,
 			data.as_ptr() as *const c_void,
 			lightgbm_sys::C_API_DTYPE_FLOAT64,
-			data.len() as i32,
+			num_feature,
 			1_i32, // is_row_major
 			lightgbm_sys::C_API_PREDICT_NORMAL,
 			0_i32,  // start_iteration
@@ -284,7 +1079,7 @@ impl Booster {
 	pub fn single_row_predictor<'a>(&'a self) -> Result<SingleRowPredictor<'a>> {
 		let num_feature: i32 = self.num_feature()?;
 		let input_size: usize = num_feature.try_into().map_err(|_| {
-			Error::new("Number of features returned by LGBM C API doesn't fit in a usize")
+			Error::ffi("Number of features returned by LGBM C API doesn't fit in a usize")
 		})?;
 
 		// https://github.com/microsoft/LightGBM/issues/6142
@@ -292,7 +1087,7 @@ impl Booster {
 		// be synchronized, this fixes the issue.
 		let _guard = self.race_workaround_mutex.lock().unwrap();
 
-		let output_size = self.predict_output_len(1)?;
+		let output_size = self.predict_output_len(1, PredictType::Normal)?;
 
 		let mut handle = std::ptr::null_mut();
 
@@ -315,20 +1110,53 @@ impl Booster {
 		})
 	}
 
+	/// Predict results for given data, one row at a time.
+	///
+	/// This yields the same values as [`predict`](Self::predict) split by row, but only holds
+	/// a single row's output in memory at a time. It goes through the
+	/// [`SingleRowPredictor`], so it trades one FFI call per row for memory.
+	pub fn predict_rows_iter<'a>(
+		&'a self,
+		data: &'a [f64],
+		n_rows: usize,
+	) -> impl Iterator<Item = Result<Vec<f64>>> + 'a {
+		let predictor = self.single_row_predictor().and_then(|predictor| {
+			if predictor.input_size == 0 {
+				return Err(Error::invalid_input("model has no features"));
+			}
+			check_data_len(data, n_rows, predictor.input_size)?;
+			Ok(predictor)
+		});
+		let (predictor, error) = match predictor {
+			Ok(predictor) => (Some(predictor), None),
+			Err(e) => (None, Some(Err(e))),
+		};
+		error
+			.into_iter()
+			.chain(predictor.into_iter().flat_map(move |predictor| {
+				data.chunks_exact(predictor.input_size)
+					.map(move |row| predictor.predict(row))
+			}))
+	}
+
 	/// Get the size of the output array that will be required for this prediction
-	pub(crate) fn predict_output_len(&self, n_rows: i32) -> Result<usize> {
+	pub(crate) fn predict_output_len(
+		&self,
+		n_rows: i32,
+		predict_type: PredictType,
+	) -> Result<usize> {
 		let mut output_size: i64 = 0;
 		lgbm_call!(lightgbm_sys::LGBM_BoosterCalcNumPredict(
 			self.handle,
 			n_rows,
-			lightgbm_sys::C_API_PREDICT_NORMAL, // predict_type
-			0_i32,                              // start_iteration
-			-1_i32,                             // num_iteration
+			predict_type.as_c_api(),
+			0_i32,  // start_iteration
+			-1_i32, // num_iteration
 			&mut output_size
 		))?;
 		output_size
 			.try_into()
-			.map_err(|_| Error::new("Output size returned by LGBM C API doesn't fit in a usize"))
+			.map_err(|_| Error::ffi("Output size returned by LGBM C API doesn't fit in a usize"))
 	}
 
 	/// Get Feature Num.
@@ -341,53 +1169,178 @@ impl Booster {
 		Ok(out_len)
 	}
 
-	fn _feature_names(&self, num_features: i32, feature_name_size: usize) -> Result<FeatureNames> {
-		let mut features = (0..num_features)
-			.map(|_| (0..feature_name_size).map(|_| 0).collect::<Vec<u8>>())
-			.collect::<Vec<_>>();
+	/// Get the number of classes of the model, 1 for regression and binary classification.
+	pub fn num_classes(&self) -> Result<i32> {
+		let mut out_len = 0;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumClasses(
+			self.handle,
+			&mut out_len
+		))?;
+		Ok(out_len)
+	}
 
-		let out_strs = features
-			.iter_mut()
-			.map(|v| v.as_mut_ptr())
-			.collect::<Vec<_>>();
+	/// Get the objective the model was trained with, as recorded in the model, e.g. `binary
+	/// sigmoid:1` or `multiclass num_class:3`.
+	///
+	/// This allows handling the output of models loaded from a file without knowing how they
+	/// were trained.
+	pub fn objective(&self) -> Result<String> {
+		let dump: Value = serde_json::from_slice(&self.dump_model_json()?)
+			.map_err(|e| Error::from_other("failed to parse model dump", e))?;
+		match dump.get("objective").and_then(Value::as_str) {
+			Some(objective) => Ok(objective.to_owned()),
+			None => Err(Error::ffi("model dump without objective")),
+		}
+	}
 
-		let mut num_feature_names = 0;
-		let mut actual_feature_name_len = 0;
+	/// Get the kind of task the model solves, derived from its [objective](Self::objective).
+	///
+	/// Objectives for custom objective functions can't be classified, and are reported as
+	/// errors.
+	pub fn task(&self) -> Result<Task> {
+		let objective = self.objective()?;
+		match objective.split_whitespace().next().unwrap_or_default() {
+			"binary"
+			| "multiclass"
+			| "multiclassova"
+			| "cross_entropy"
+			| "cross_entropy_lambda" => Ok(Task::Classification {
+				num_class: self.num_classes()?,
+			}),
+			"regression" | "regression_l1" | "huber" | "fair" | "poisson" | "quantile" | "mape"
+			| "gamma" | "tweedie" => Ok(Task::Regression),
+			"lambdarank" | "rank_xendcg" => Ok(Task::Ranking),
+			_ => Err(Error::invalid_input(format!(
+				"can't tell the task of a model with objective {objective}"
+			))),
+		}
+	}
 
-		lgbm_call!(lightgbm_sys::LGBM_BoosterGetFeatureNames(
+	/// Get the number of boosting iterations of the model.
+	pub fn current_iteration(&self) -> Result<i32> {
+		let mut out_iteration = 0;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetCurrentIteration(
 			self.handle,
-			num_features,
-			&mut num_feature_names,
-			feature_name_size,
-			&mut actual_feature_name_len,
-			out_strs.as_ptr() as *mut *mut c_char
+			&mut out_iteration
 		))?;
+		Ok(out_iteration)
+	}
 
-		Ok(FeatureNames {
-			features,
-			actual_feature_name_len,
-			num_feature_names,
-		})
+	/// Get the total number of trees in the model.
+	///
+	/// Multiclass models have one tree per class and per iteration.
+	pub fn total_num_trees(&self) -> Result<usize> {
+		let mut out_models = 0;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterNumberOfTotalModel(
+			self.handle,
+			&mut out_models
+		))?;
+		out_models
+			.try_into()
+			.map_err(|_| Error::ffi("negative number of trees returned by LGBM C API"))
 	}
 
-	/// Get Feature Names.
-	pub fn feature_names(&self) -> Result<Vec<String>> {
-		let num_features = self.num_feature()?;
+	/// Get the minimum and maximum raw score the model can predict.
+	///
+	/// These are the sums over the trees of their lowest and highest leaf values. For models
+	/// with a single output, every [`RawScore`](PredictType::RawScore) prediction lies within
+	/// them, although they may not be reached by any row. For multiclass models they sum the
+	/// trees of all classes, while the raw score of each class only sums its own trees, so
+	/// they don't bound the raw scores of the classes.
+	pub fn prediction_bounds(&self) -> Result<(f64, f64)> {
+		let mut lower = 0_f64;
+		let mut upper = 0_f64;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetLowerBoundValue(
+			self.handle,
+			&mut lower
+		))?;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetUpperBoundValue(
+			self.handle,
+			&mut upper
+		))?;
+		Ok((lower, upper))
+	}
+
+	/// Add the trees of `other` to this model, before its own trees, e.g. to serve models
+	/// trained on different shards of data as a single model.
+	///
+	/// This concatenates trees, it doesn't average models: predictions of the merged model sum
+	/// the raw scores of both models. Both models should share their objective and features;
+	/// the number of features and of classes are checked. As the trees of `other` come first,
+	/// they count as the initial iterations of the merged model, e.g. for
+	/// [`truncate`](Self::truncate). The merged model is meant for prediction: scores of the
+	/// training and validation data attached to this booster aren't updated.
+	pub fn merge(&mut self, other: &Booster) -> Result<()> {
+		let (num_feature, other_num_feature) = (self.num_feature()?, other.num_feature()?);
+		if num_feature != other_num_feature {
+			return Err(Error::invalid_input(format!(
+				"can't merge a model with {other_num_feature} features into a model with \
+					{num_feature} features"
+			)));
+		}
+		let (num_classes, other_num_classes) = (self.num_classes()?, other.num_classes()?);
+		if num_classes != other_num_classes {
+			return Err(Error::invalid_input(format!(
+				"can't merge a model with {other_num_classes} classes into a model with \
+					{num_classes} classes"
+			)));
+		}
+		lgbm_call!(lightgbm_sys::LGBM_BoosterMerge(self.handle, other.handle))?;
+		Ok(())
+	}
+
+	fn _strings(
+		&self,
+		getter: StringListGetter,
+		len: i32,
+		string_size: usize,
+	) -> Result<StringList> {
+		let mut strings = (0..len)
+			.map(|_| (0..string_size).map(|_| 0).collect::<Vec<u8>>())
+			.collect::<Vec<_>>();
 
-		const DEFAULT_MAX_FEATURE_NAME_SIZE: usize = 64;
-		let mut feature_result =
-			self._feature_names(num_features, DEFAULT_MAX_FEATURE_NAME_SIZE)?;
+		let out_strs = strings
+			.iter_mut()
+			.map(|v| v.as_mut_ptr())
+			.collect::<Vec<_>>();
+
+		let mut num_strings = 0;
+		let mut actual_string_len = 0;
+
+		lgbm_call!(getter(
+			self.handle,
+			len,
+			&mut num_strings,
+			string_size,
+			&mut actual_string_len,
+			out_strs.as_ptr() as *mut *mut c_char
+		))?;
 
-		// If the feature name size was larger than the default max, try again with the actual size
-		if feature_result.actual_feature_name_len > DEFAULT_MAX_FEATURE_NAME_SIZE {
-			feature_result =
-				self._feature_names(num_features, feature_result.actual_feature_name_len)?;
+		Ok(StringList {
+			strings,
+			actual_string_len,
+			num_strings,
+		})
+	}
+
+	/// Get a list of strings from the C API, such as feature names or eval names.
+	fn string_list(&self, getter: StringListGetter, len: i32) -> Result<Vec<String>> {
+		const DEFAULT_MAX_STRING_SIZE: usize = 64;
+		let mut result = self._strings(getter, len, DEFAULT_MAX_STRING_SIZE)?;
+
+		// If the string size was larger than the default max, try again with the actual size
+		if result.actual_string_len > DEFAULT_MAX_STRING_SIZE {
+			result = self._strings(getter, len, result.actual_string_len)?;
 		}
 
-		Ok(feature_result
-			.features
+		let num_strings = result
+			.num_strings
+			.try_into()
+			.map_err(|_| Error::ffi("negative number of strings returned by LGBM C API"))?;
+		Ok(result
+			.strings
 			.into_iter()
-			.take(feature_result.num_feature_names as usize)
+			.take(num_strings)
 			.map(|s| unsafe {
 				CStr::from_ptr(s.as_ptr() as *const i8)
 					.to_string_lossy()
@@ -396,23 +1349,315 @@ impl Booster {
 			.collect())
 	}
 
+	/// Get Feature Names.
+	pub fn feature_names(&self) -> Result<Vec<String>> {
+		self.string_list(
+			lightgbm_sys::LGBM_BoosterGetFeatureNames,
+			self.num_feature()?,
+		)
+	}
+
+	/// Add a validation dataset, on which metrics can then be computed with
+	/// [`eval`](Self::eval).
+	///
+	/// The validation dataset has to be constructed with the training dataset as reference
+	/// (e.g. with [`Dataset::from_mat_with_reference`]) so that they share the same binning.
+	/// Weights set on the validation dataset (with [`Dataset::set_weights`]) are used when
+	/// computing metrics on it.
+	///
+	/// This is only possible on a booster that was trained from a dataset, not on one loaded
+	/// from a model file.
+	pub fn add_valid_data(&mut self, dataset: Dataset) -> Result<()> {
+		if self.train_data.is_none() {
+			return Err(Error::invalid_input(
+				"validation data can only be added to a booster trained from a dataset",
+			));
+		}
+		lgbm_call!(lightgbm_sys::LGBM_BoosterAddValidData(
+			self.handle,
+			dataset.handle
+		))?;
+		// LightGBM keeps a pointer to the dataset, so it has to live as long as the booster
+		self.valid_data.push(dataset);
+		Ok(())
+	}
+
+	/// Get the number of metrics computed by [`eval`](Self::eval), i.e. the number of values
+	/// it returns for each dataset.
+	pub fn num_eval_metrics(&self) -> Result<usize> {
+		let mut out_len = 0;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetEvalCounts(
+			self.handle,
+			&mut out_len
+		))?;
+		out_len
+			.try_into()
+			.map_err(|_| Error::ffi("number of metrics negative"))
+	}
+
+	/// Get the names of the metrics computed by [`eval`](Self::eval).
+	pub fn eval_names(&self) -> Result<Vec<String>> {
+		let num_eval_metrics = self
+			.num_eval_metrics()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of metrics doesn't fit into an i32"))?;
+		self.string_list(lightgbm_sys::LGBM_BoosterGetEvalNames, num_eval_metrics)
+	}
+
+	/// Compute the configured metrics on the training data (`data_idx` 0) or on the validation
+	/// dataset added with [`add_valid_data`](Self::add_valid_data) (`data_idx` 1 for the
+	/// first one, and so on).
+	///
+	/// Metrics are weighted by the dataset's weights if they have been set.
+	pub fn eval(&self, data_idx: usize) -> Result<Vec<(String, f64)>> {
+		let data_idx = data_idx
+			.try_into()
+			.map_err(|_| Error::conversion("data index doesn't fit into an i32"))?;
+		let eval_names = self.eval_names()?;
+		let mut out_results = vec![0_f64; self.num_eval_metrics()?];
+		let mut out_len = 0;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetEval(
+			self.handle,
+			data_idx,
+			&mut out_len,
+			out_results.as_mut_ptr()
+		))?;
+		assert!(
+			usize::try_from(out_len).is_ok_and(|l| l == out_results.len()),
+			"Unexpected written output length"
+		);
+		Ok(eval_names.into_iter().zip(out_results).collect())
+	}
+
+	/// Compute the configured metrics like [`eval`](Self::eval), checking that `valid_index`
+	/// refers to an attached dataset: 0 is the training data, and `i` is the `i`-th validation
+	/// dataset added with [`add_valid_data`](Self::add_valid_data).
+	///
+	/// Metrics are kept up to date during training, so this doesn't predict again.
+	pub fn eval_valid(&self, valid_index: usize) -> Result<Vec<(String, f64)>> {
+		if self.train_data.is_none() {
+			return Err(Error::invalid_input(
+				"metrics can only be computed on a booster trained from a dataset",
+			));
+		}
+		if valid_index > self.valid_data.len() {
+			return Err(Error::invalid_input(format!(
+				"data index {valid_index} is out of bounds, the booster has {} validation datasets",
+				self.valid_data.len()
+			)));
+		}
+		self.eval(valid_index)
+	}
+
+	/// Compute the configured metrics like [`eval_valid`](Self::eval_valid), keyed by metric
+	/// name, e.g. to look up the AUC with [`EvalResults::auc`].
+	pub fn eval_results(&self, valid_index: usize) -> Result<EvalResults> {
+		Ok(self.eval_valid(valid_index)?.into_iter().collect())
+	}
+
+	/// Get the current predictions of the model on the training data (`data_idx` 0) or on
+	/// the validation dataset added with [`add_valid_data`](Self::add_valid_data) (`data_idx`
+	/// 1 for the first one, and so on).
+	///
+	/// These are kept up to date during training, so this is much cheaper than predicting.
+	/// Like [`Dataset::set_init_score`], multiclass predictions are laid out class-major.
+	pub fn get_predict(&self, data_idx: usize) -> Result<Vec<f64>> {
+		if self.train_data.is_none() {
+			return Err(Error::invalid_input(
+				"predictions are only tracked on a booster trained from a dataset",
+			));
+		}
+		if data_idx > self.valid_data.len() {
+			return Err(Error::invalid_input(format!(
+				"data index {data_idx} is out of bounds, the booster has {} validation datasets",
+				self.valid_data.len()
+			)));
+		}
+		let data_idx = data_idx
+			.try_into()
+			.map_err(|_| Error::conversion("data index doesn't fit into an i32"))?;
+		let mut out_len = 0_i64;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumPredict(
+			self.handle,
+			data_idx,
+			&mut out_len
+		))?;
+		let mut out_result = vec![
+			0_f64;
+			out_len.try_into().map_err(|_| Error::ffi(
+				"prediction length returned by C API is negative"
+			))?
+		];
+		lgbm_call!(lightgbm_sys::LGBM_BoosterGetPredict(
+			self.handle,
+			data_idx,
+			&mut out_len,
+			out_result.as_mut_ptr()
+		))?;
+		assert!(
+			usize::try_from(out_len).is_ok_and(|l| l == out_result.len()),
+			"Unexpected written output length"
+		);
+		Ok(out_result)
+	}
+
 	// Get Feature Importance
 	pub fn feature_importance(&self) -> Result<Vec<f64>> {
-		let num_feature = self.num_feature()?;
-		let out_result: Vec<f64> = vec![Default::default(); num_feature as usize];
+		self.feature_importance_of_type(0, lightgbm_sys::C_API_FEATURE_IMPORTANCE_SPLIT)
+	}
+
+	/// Get both the number of splits and the total gain of each feature, along with its name.
+	///
+	/// Only the first `num_iteration` iterations are taken into account, or all of them if
+	/// `num_iteration <= 0`.
+	pub fn feature_importances(&self, num_iteration: i32) -> Result<Vec<FeatureImportance>> {
+		let num_iteration = num_iteration.max(0);
+		let split_counts = self.feature_importance_of_type(
+			num_iteration,
+			lightgbm_sys::C_API_FEATURE_IMPORTANCE_SPLIT,
+		)?;
+		let total_gains = self.feature_importance_of_type(
+			num_iteration,
+			lightgbm_sys::C_API_FEATURE_IMPORTANCE_GAIN,
+		)?;
+		let names = self.feature_names()?;
+		if names.len() != split_counts.len() {
+			return Err(Error::ffi(format!(
+				"got {} feature names from LGBM C API but {} feature importances",
+				names.len(),
+				split_counts.len()
+			)));
+		}
+		Ok(names
+			.into_iter()
+			.zip(split_counts.into_iter().zip(total_gains))
+			.enumerate()
+			.map(
+				|(index, (name, (split_count, total_gain)))| FeatureImportance {
+					index,
+					name,
+					split_count: split_count as u64,
+					total_gain,
+				},
+			)
+			.collect())
+	}
+
+	/// Get the importance of each feature paired with its name, sorted from the most to the
+	/// least important.
+	///
+	/// Features with the same importance are in the order of their index. Only the first
+	/// `num_iteration` iterations are taken into account, or all of them if
+	/// `num_iteration <= 0`.
+	pub fn feature_importance_report(
+		&self,
+		importance_type: ImportanceType,
+		num_iteration: i32,
+	) -> Result<Vec<(String, f64)>> {
+		let importances =
+			self.feature_importance_of_type(num_iteration.max(0), importance_type.as_c_api())?;
+		let names = self.feature_names()?;
+		if names.len() != importances.len() {
+			return Err(Error::ffi(format!(
+				"got {} feature names from LGBM C API but {} feature importances",
+				names.len(),
+				importances.len()
+			)));
+		}
+		let mut report: Vec<(String, f64)> = names.into_iter().zip(importances).collect();
+		report.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+		Ok(report)
+	}
+
+	/// Compute the permutation importance of each feature on held-out data.
+	///
+	/// For each feature, its column of `data` (`n_rows` rows in row-major order) is shuffled
+	/// across rows, and the importance is how much `metric` degrades compared to the
+	/// predictions on the original data, averaged over `n_repeats` shuffles. `metric` is given
+	/// the normal predictions and `labels`, and should be higher when predictions are better
+	/// (e.g. accuracy or AUC, or a negated loss).
+	///
+	/// Unlike the [split and gain importances](Self::feature_importance_report), this measures
+	/// how much the model actually relies on each feature on the given data. Shuffles are
+	/// deterministic for a given `seed`.
+	pub fn permutation_importance(
+		&self,
+		data: &[f64],
+		n_rows: usize,
+		labels: &[f32],
+		metric: impl Fn(&[f64], &[f32]) -> f64,
+		n_repeats: usize,
+		seed: u64,
+	) -> Result<Vec<f64>> {
+		if n_repeats == 0 {
+			return Err(Error::invalid_input("n_repeats should be at least 1"));
+		}
+		if labels.len() != n_rows {
+			return Err(Error::invalid_input(format!(
+				"got {} labels for {n_rows} rows",
+				labels.len()
+			)));
+		}
+		let num_feature: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::ffi("negative number of features returned by LGBM C API"))?;
+		if data.len() != n_rows * num_feature {
+			return Err(Error::invalid_input(format!(
+				"data len ({}) doesn't match {n_rows} rows of {num_feature} features",
+				data.len()
+			)));
+		}
+		let baseline = metric(
+			&self.predict_with_type(data, n_rows, PredictType::Normal)?,
+			labels,
+		);
+
+		let mut rng = crate::dataset::SplitMix64(seed);
+		let mut permuted = data.to_vec();
+		let mut importances = Vec::with_capacity(num_feature);
+		for feature in 0..num_feature {
+			let mut degradation = 0.;
+			for _ in 0..n_repeats {
+				// Fisher-Yates shuffle of the column
+				for i in (1..n_rows).rev() {
+					let j = (rng.next() % (i as u64 + 1)) as usize;
+					permuted.swap(i * num_feature + feature, j * num_feature + feature);
+				}
+				let predictions = self.predict_with_type(&permuted, n_rows, PredictType::Normal)?;
+				degradation += baseline - metric(&predictions, labels);
+			}
+			importances.push(degradation / n_repeats as f64);
+			// restore the column before shuffling the next one
+			for row in 0..n_rows {
+				permuted[row * num_feature + feature] = data[row * num_feature + feature];
+			}
+		}
+		Ok(importances)
+	}
+
+	fn feature_importance_of_type(
+		&self,
+		num_iteration: i32,
+		importance_type: i32,
+	) -> Result<Vec<f64>> {
+		let num_feature: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::ffi("negative number of features returned by LGBM C API"))?;
+		let out_result: Vec<f64> = vec![Default::default(); num_feature];
 		lgbm_call!(lightgbm_sys::LGBM_BoosterFeatureImportance(
 			self.handle,
-			0_i32,
-			0_i32,
+			num_iteration,
+			importance_type,
 			out_result.as_ptr() as *mut c_double
 		))?;
 		Ok(out_result)
 	}
 
 	/// Save model to file.
-	pub fn save_file(&self, filename: &str) -> Result<()> {
-		let filename_str =
-			CString::new(filename).map_err(|e| Error::from_other("failed to create cstring", e))?;
+	pub fn save_file(&self, filename: impl AsRef<Path>) -> Result<()> {
+		let filename_str = crate::path_to_cstring(filename.as_ref())?;
 		lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModel(
 			self.handle,
 			0_i32,
@@ -425,12 +1670,35 @@ impl Booster {
 
 	/// Save model to string. This returns the same content that `save_file` writes into a file.
 	pub fn save_string(&self) -> Result<Vec<u8>> {
+		self.save_string_with_num_iteration(-1)
+	}
+
+	/// Save model to file like [`save_file`](Self::save_file), with leaf values and split
+	/// statistics rounded to `decimals` decimal places to make the file smaller.
+	///
+	/// Rounding costs accuracy: each tree's output moves by up to `0.5 * 10^-decimals`, so raw
+	/// scores may move by up to that times the number of trees. Split thresholds are kept at
+	/// full precision, so rows still reach the same leaves. The rounded model is checked to
+	/// load before being written.
+	pub fn save_model_rounded(&self, filename: impl AsRef<Path>, decimals: usize) -> Result<()> {
+		let model = String::from_utf8(self.save_string()?)
+			.map_err(|e| Error::from_other("model string is not valid UTF-8", e))?;
+		let rounded = round_model_string(&model, decimals);
+		Self::from_string(rounded.as_bytes())?;
+		let filename = filename.as_ref();
+		std::fs::write(filename, rounded)
+			.map_err(|e| Error::io(format!("failed to write {}: {e}", filename.display())))
+	}
+
+	/// Save the first `num_iteration` iterations of the model to string, or all of them if
+	/// `num_iteration <= 0`.
+	fn save_string_with_num_iteration(&self, num_iteration: i32) -> Result<Vec<u8>> {
 		// get nessesary buffer size
 		let mut out_size = 0_i64;
 		lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
 			self.handle,
 			0_i32,
-			-1_i32,
+			num_iteration,
 			0_i32,
 			0,
 			&mut out_size as *mut _,
@@ -440,14 +1708,14 @@ impl Booster {
 		// write data to buffer and convert
 		let mut buffer = vec![
 			0u8;
-			out_size.try_into().map_err(|_| Error::new(
+			out_size.try_into().map_err(|_| Error::ffi(
 				"string size returned by C API is negative"
 			))?
 		];
 		lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
 			self.handle,
 			0_i32,
-			-1_i32,
+			num_iteration,
 			0_i32,
 			out_size,
 			&mut out_size as *mut _,
@@ -464,6 +1732,155 @@ impl Booster {
 		Ok(cstring.into_bytes())
 	}
 
+	/// Drop the trees of the iterations after the first `num_iteration` ones.
+	///
+	/// Unlike limiting the number of iterations at prediction time, this actually removes the
+	/// trees from the model, so saving it gives a smaller model. The model is rebuilt from its
+	/// truncated serialization, so it's no longer attached to its training and validation
+	/// datasets: training can't continue afterwards.
+	pub fn truncate(&mut self, num_iteration: i32) -> Result<()> {
+		let current_iteration = self.current_iteration()?;
+		if num_iteration <= 0 || num_iteration > current_iteration {
+			return Err(Error::invalid_input(format!(
+				"can't truncate to {num_iteration} iterations, the model has {current_iteration}"
+			)));
+		}
+		let mut truncated =
+			Self::from_string(&self.save_string_with_num_iteration(num_iteration)?)?;
+		truncated.param_overrides = std::mem::take(&mut self.param_overrides);
+		*self = truncated;
+		Ok(())
+	}
+
+	/// Refit the leaf values of the model to `n_rows` rows of new data with labels `label`,
+	/// keeping the structure of the trees.
+	///
+	/// Rows are routed to the leaves of each tree as when predicting, then the value of each
+	/// leaf is recomputed from the gradients of the rows reaching it, in order of the trees,
+	/// e.g. to adapt a model to data that drifted without growing new trees. LightGBM blends
+	/// the values: a refitted leaf is
+	/// `refit_decay_rate * old value + (1 - refit_decay_rate) * new value`, with a
+	/// `refit_decay_rate` of 0.9 by default.
+	///
+	/// The LightGBM C API needs the labels, which is why they are required. The gradients
+	/// are computed with the model's [objective](Self::objective), and new leaf values with
+	/// the regularization it was trained with (`lambda_l1`, `lambda_l2`, `max_delta_step`,
+	/// `path_smooth`) and its `refit_decay_rate`, as recorded in the
+	/// [model](Self::training_params).
+	///
+	/// Afterwards, the booster is attached to the refit data instead of its training and
+	/// validation datasets, with these parameters and otherwise default ones:
+	/// [`update_one_iter`](Self::update_one_iter) continues boosting on the refit data, and
+	/// [`eval`](Self::eval) only has that data (`data_idx` 0) until validation datasets are
+	/// [added](Self::add_valid_data) again.
+	pub fn refit_on(&mut self, data: &[f64], n_rows: usize, label: &[f32]) -> Result<()> {
+		if n_rows == 0 {
+			return Err(Error::invalid_input("can't refit a model on 0 rows"));
+		}
+		// keep the parameters that LightGBM computes refitted leaf values with
+		let training_params = self.training_params()?;
+		let mut params = Params::new();
+		for key in REFIT_PARAMS {
+			if let Some(value) = training_params.get(key) {
+				params = params.set(key, value);
+			}
+		}
+		let objective = self.objective()?;
+		let mut tokens = objective.split_whitespace();
+		params = params.set("objective", tokens.next().unwrap_or_default());
+		for token in tokens {
+			params = match token.split_once(':') {
+				Some((key, value)) => params.set(key, value),
+				None if token == "sqrt" => params.set("reg_sqrt", true),
+				None => params,
+			};
+		}
+
+		let leaf_preds: Vec<i32> = self
+			.predict_with_type(data, n_rows, PredictType::LeafIndex)?
+			.into_iter()
+			.map(|leaf_index| leaf_index as i32)
+			.collect();
+		let nrow = n_rows
+			.try_into()
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		let ncol = self
+			.total_num_trees()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of trees doesn't fit into an i32"))?;
+
+		let mut dataset = Dataset::from_mat(data, n_rows, label)?;
+		let feature_names = self.feature_names()?;
+		dataset.set_feature_names(&feature_names.iter().map(String::as_str).collect::<Vec<_>>())?;
+		let mut refitted = Self::create(dataset, &params)?;
+		refitted.merge(self)?;
+		lgbm_call!(lightgbm_sys::LGBM_BoosterRefit(
+			refitted.handle,
+			leaf_preds.as_ptr(),
+			nrow,
+			ncol
+		))?;
+		refitted.param_overrides = std::mem::take(&mut self.param_overrides);
+		*self = refitted;
+		Ok(())
+	}
+
+	/// Get whether each feature was handled as numerical or categorical by LightGBM, in the
+	/// order of the features of the model.
+	///
+	/// This is read from the `feature_infos` of the model, which lists the categories of
+	/// categorical features and the range of numerical ones. Features unused by LightGBM,
+	/// e.g. because they are constant, are reported as numerical.
+	pub fn feature_types(&self) -> Result<Vec<FeatureType>> {
+		let model = String::from_utf8(self.save_string()?)
+			.map_err(|e| Error::from_other("model string returned by C API is not UTF-8", e))?;
+		let feature_infos = model
+			.lines()
+			.take_while(|line| !line.starts_with("Tree="))
+			.find_map(|line| line.strip_prefix("feature_infos="))
+			.ok_or_else(|| Error::ffi("model string has no feature_infos"))?;
+		Ok(feature_infos
+			.split(' ')
+			.map(|info| match info {
+				"none" => FeatureType::Numerical,
+				info if info.starts_with('[') => FeatureType::Numerical,
+				_ => FeatureType::Categorical,
+			})
+			.collect())
+	}
+
+	/// Get the parameters the model was trained with, as recorded in the model.
+	///
+	/// LightGBM only persists the values of the core parameters, after resolving aliases:
+	/// parameters that only affect dataset construction or I/O, for instance, can't be
+	/// recovered this way. Values are formatted as in the model file, e.g. lists are
+	/// comma-separated.
+	pub fn training_params(&self) -> Result<HashMap<String, String>> {
+		let model = String::from_utf8(self.save_string()?)
+			.map_err(|e| Error::from_other("model string returned by C API is not UTF-8", e))?;
+		Ok(model
+			.lines()
+			.skip_while(|&line| line != "parameters:")
+			.skip(1)
+			.take_while(|&line| line != "end of parameters")
+			.filter_map(|line| {
+				let (key, value) = line
+					.strip_prefix('[')?
+					.strip_suffix(']')?
+					.split_once(": ")?;
+				Some((key.to_owned(), value.to_owned()))
+			})
+			.collect())
+	}
+
+	/// Estimate the memory used by the model, as the size of its text serialization in bytes.
+	///
+	/// LightGBM doesn't report its in-memory footprint, but it is roughly proportional to the
+	/// size of the model file, which is dominated by the trees.
+	pub fn model_size_bytes(&self) -> Result<usize> {
+		Ok(self.save_string()?.len())
+	}
+
 	/// Save model to string. This returns the same content that `save_file` writes into a file.
 	pub fn dump_model_json(&self) -> Result<Vec<u8>> {
 		// get nessesary buffer size
@@ -481,7 +1898,7 @@ impl Booster {
 		// write data to buffer and convert
 		let mut buffer = vec![
 			0u8;
-			out_size.try_into().map_err(|_| Error::new(
+			out_size.try_into().map_err(|_| Error::ffi(
 				"string size returned by C API is negative"
 			))?
 		];
@@ -504,116 +1921,1528 @@ impl Booster {
 			.map_err(|e| Error::from_other("Parsing CString returned by C API failed", e))?;
 		Ok(cstring.into_bytes())
 	}
-}
-
-struct FeatureNames {
-	features: Vec<Vec<u8>>,
-	actual_feature_name_len: usize,
-	num_feature_names: i32,
-}
-
-#[cfg(test)]
-mod tests {
-	use {
-		super::*,
-		serde_json::json,
-		std::{fs, path::Path},
-	};
 
-	fn _read_train_file() -> Result<Dataset> {
-		Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+	/// Get the path that `row` takes through each tree of the model, explaining how the
+	/// prediction for that row is made.
+	///
+	/// Paths are computed from the JSON model dump, routing the row like LightGBM does,
+	/// including for missing values. For categorical splits, the row goes left if its value
+	/// is one of the split's [categories](PathNode::categories). NaN and negative values are
+	/// never part of these categories so they go right.
+	pub fn decision_path(&self, row: &[f64]) -> Result<Vec<TreePath>> {
+		let n_features: usize = self
+			.num_feature()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an usize"))?;
+		if row.len() != n_features {
+			return Err(Error::invalid_input(format!(
+				"row len ({}) is not n_features ({n_features})",
+				row.len()
+			)));
+		}
+		self.tree_structures()?
+			.iter()
+			.map(|tree| {
+				let mut nodes = Vec::new();
+				let mut node = tree;
+				while node.get("split_index").is_some() {
+					let path_node = route(node, row)?;
+					node = &node[if path_node.went_left {
+						"left_child"
+					} else {
+						"right_child"
+					}];
+					nodes.push(path_node);
+				}
+				Ok(TreePath {
+					nodes,
+					leaf_index: node
+						.get("leaf_index")
+						.and_then(Value::as_i64)
+						.unwrap_or(0)
+						.try_into()
+						.map_err(|_| Error::conversion("leaf index doesn't fit into an i32"))?,
+					leaf_value: node["leaf_value"]
+						.as_f64()
+						.ok_or_else(|| Error::ffi("leaf without leaf_value in model dump"))?,
+				})
+			})
+			.collect()
 	}
 
-	fn _train_booster(params: &Value) -> Booster {
-		let dataset = _read_train_file().unwrap();
-		Booster::train(dataset, params).unwrap()
+	/// Get the trees of the model as Rust values, parsed from the JSON model dump.
+	///
+	/// This is a building block for exporting the model to other formats or evaluating it in
+	/// other runtimes. With the `serde` feature, [`Tree`] can be serialized. The raw score of
+	/// a row is the sum of the values of the leaves it reaches in each tree (for multiclass
+	/// models, trees are interleaved by class).
+	pub fn export_trees(&self) -> Result<Vec<Tree>> {
+		self.tree_structures()?
+			.iter()
+			.map(|tree_structure| {
+				let mut nodes = Vec::new();
+				flatten_tree(tree_structure, &mut nodes)?;
+				Ok(Tree { nodes })
+			})
+			.collect()
 	}
 
-	fn _default_params() -> Value {
-		let params = json! {
-			{
-				"num_iterations": 1,
-				"objective": "binary",
-				"metric": "auc",
-				"data_random_seed": 0
+	/// Get the number of training data points that fell into each leaf of the tree at
+	/// `tree_index`, ordered by leaf index.
+	pub fn leaf_counts(&self, tree_index: usize) -> Result<Vec<i32>> {
+		fn collect_leaves(node: &Value, leaves: &mut Vec<(i64, i32)>) -> Result<()> {
+			if node.get("split_index").is_some() {
+				for child in ["left_child", "right_child"] {
+					collect_leaves(&node[child], leaves)?;
+				}
+				return Ok(());
 			}
-		};
-		params
+			let leaf_index = node.get("leaf_index").and_then(Value::as_i64).unwrap_or(0);
+			let leaf_count = node
+				.get("leaf_count")
+				.and_then(Value::as_i64)
+				.ok_or_else(|| Error::ffi("leaf without leaf_count in model dump"))?
+				.try_into()
+				.map_err(|_| Error::conversion("leaf count doesn't fit into an i32"))?;
+			leaves.push((leaf_index, leaf_count));
+			Ok(())
+		}
+
+		let mut leaves = Vec::new();
+		collect_leaves(&self.tree_structure(tree_index)?, &mut leaves)?;
+		leaves.sort_unstable_by_key(|&(leaf_index, _)| leaf_index);
+		Ok(leaves
+			.into_iter()
+			.map(|(_, leaf_count)| leaf_count)
+			.collect())
 	}
 
-	#[test]
-	fn predict() {
-		let params = json! {
-			{
-				"num_iterations": 10,
-				"objective": "binary",
-				"metric": "auc",
-				"data_random_seed": 0
+	/// Get statistics about the size of the trees of the model, see [`TreeStats`].
+	///
+	/// The depth of a tree is measured from the nesting of its nodes in the JSON model dump:
+	/// it is the number of splits from the root to its deepest leaf, so a tree with a single
+	/// leaf has depth 0.
+	pub fn tree_stats(&self) -> Result<TreeStats> {
+		/// Number of leaves and depth of the tree rooted at `node`.
+		fn leaves_and_depth(node: &Value) -> (usize, usize) {
+			if node.get("split_index").is_none() {
+				return (1, 0);
 			}
+			let (left_leaves, left_depth) = leaves_and_depth(&node["left_child"]);
+			let (right_leaves, right_depth) = leaves_and_depth(&node["right_child"]);
+			(left_leaves + right_leaves, 1 + left_depth.max(right_depth))
+		}
+
+		let trees = self.tree_structures()?;
+		let mut stats = TreeStats {
+			num_trees: trees.len(),
+			total_leaves: 0,
+			avg_depth: 0.,
+			max_depth: 0,
 		};
-		let mut bst = _train_booster(&params);
-		let features = [[0.5; 28], [0.0; 28], [0.9; 28]]
-			.into_iter()
-			.flatten()
-			.collect::<Vec<_>>();
-		let result = bst.predict(&features).unwrap();
-		let mut normalized_result: Vec<i32> = Vec::new();
-		for &r in &result {
-			normalized_result.push((r > 0.5).into());
+		let mut total_depth = 0;
+		for tree in &trees {
+			let (leaves, depth) = leaves_and_depth(tree);
+			stats.total_leaves += leaves;
+			stats.max_depth = stats.max_depth.max(depth);
+			total_depth += depth;
 		}
-		assert_eq!(normalized_result, vec![0, 0, 1]);
+		if !trees.is_empty() {
+			stats.avg_depth = total_depth as f64 / trees.len() as f64;
+		}
+		Ok(stats)
 	}
 
-	#[test]
-	fn predict_single_row() {
-		let params = json! {
-			{
-				"num_iterations": 10,
-				"objective": "binary",
-				"metric": "auc",
-				"data_random_seed": 0
+	/// Get the thresholds that the model splits `feature` at, sorted and deduplicated.
+	///
+	/// LightGBM doesn't expose the bin boundaries of a dataset, but splits on numerical
+	/// features are always made at a bin upper bound. These thresholds are thus the subset of
+	/// the bin boundaries of the training dataset that the trees actually use: bins that were
+	/// never split at are merged with their neighbours. Categorical splits are ignored.
+	pub fn bin_boundaries(&self, feature: usize) -> Result<Vec<f64>> {
+		fn collect_thresholds(node: &Value, feature: usize, thresholds: &mut Vec<f64>) {
+			if node.get("split_index").is_none() {
+				return;
 			}
-		};
-		let bst = _train_booster(&params);
-		let feature = [[0.5; 28], [0.0; 28], [0.9; 28]];
-
-		let result: Vec<f64> = feature
-			.iter()
-			.flat_map(|f| bst.predict_single_row(f).unwrap())
-			.collect();
+			let is_feature = node["split_feature"].as_u64() == Some(feature as u64);
+			if let (true, Some(threshold)) = (is_feature, node["threshold"].as_f64()) {
+				thresholds.push(threshold);
+			}
+			for child in ["left_child", "right_child"] {
+				collect_thresholds(&node[child], feature, thresholds);
+			}
+		}
 
-		let mut normalized_result: Vec<i32> = Vec::new();
-		for r in result {
-			normalized_result.push((r > 0.5).into());
+		let num_feature = self.num_feature()?;
+		if i32::try_from(feature).map_or(true, |feature| feature >= num_feature) {
+			return Err(Error::invalid_input(format!(
+				"feature index {feature} is out of bounds, the model has {num_feature} features"
+			)));
 		}
-		assert_eq!(normalized_result, vec![0, 0, 1]);
+		let mut thresholds = Vec::new();
+		for tree in self.tree_structures()? {
+			collect_thresholds(&tree, feature, &mut thresholds);
+		}
+		thresholds.sort_unstable_by(f64::total_cmp);
+		thresholds.dedup();
+		Ok(thresholds)
 	}
 
-	#[test]
+	/// Get the `tree_structure` of the tree at `tree_index` from the JSON model dump.
+	fn tree_structure(&self, tree_index: usize) -> Result<Value> {
+		let total_num_trees = self.total_num_trees()?;
+		if tree_index >= total_num_trees {
+			return Err(Error::invalid_input(format!(
+				"tree index {tree_index} is out of bounds, the model has {total_num_trees} trees"
+			)));
+		}
+		self.tree_structures()?
+			.into_iter()
+			.nth(tree_index)
+			.ok_or_else(|| Error::ffi("fewer trees than expected in model dump"))
+	}
+
+	/// Get the `tree_structure` of every tree from the JSON model dump.
+	fn tree_structures(&self) -> Result<Vec<Value>> {
+		let mut dump: Value = serde_json::from_slice(&self.dump_model_json()?)
+			.map_err(|e| Error::from_other("failed to parse model dump", e))?;
+		let tree_info = match dump.get_mut("tree_info").map(Value::take) {
+			Some(Value::Array(tree_info)) => tree_info,
+			_ => return Err(Error::ffi("model dump without tree_info")),
+		};
+		tree_info
+			.into_iter()
+			.map(|mut tree| match tree.get_mut("tree_structure") {
+				Some(tree_structure) => Ok(tree_structure.take()),
+				None => Err(Error::ffi("tree without tree_structure in model dump")),
+			})
+			.collect()
+	}
+}
+
+/// Predict results for `n_rows` rows of data with each of `models`, and average them.
+///
+/// All models must have the same output shape, e.g. the same number of classes. If `weights`
+/// are given there must be one per model, and the result is the weighted average of the
+/// predictions. Weights must be non-negative, and not all zero.
+pub fn blend_predictions(
+	models: &[&Booster],
+	data: &[f64],
+	n_rows: usize,
+	weights: Option<&[f64]>,
+) -> Result<Vec<f64>> {
+	if models.is_empty() {
+		return Err(Error::invalid_input("no models to blend"));
+	}
+	if let Some(weights) = weights {
+		if weights.len() != models.len() {
+			return Err(Error::invalid_input(format!(
+				"got {} weights for {} models",
+				weights.len(),
+				models.len()
+			)));
+		}
+		if weights.iter().any(|&w| w.is_nan() || w < 0.) || weights.iter().all(|&w| w == 0.) {
+			return Err(Error::invalid_input(
+				"weights should be non-negative, and not all zero",
+			));
+		}
+	}
+	let weight = |i: usize| weights.map_or(1., |weights| weights[i]);
+	let mut blended = Vec::new();
+	for (i, model) in models.iter().enumerate() {
+		let output = model.predict_with_type(data, n_rows, PredictType::Normal)?;
+		if i == 0 {
+			blended = vec![0.; output.len()];
+		} else if output.len() != blended.len() {
+			return Err(Error::invalid_input(format!(
+				"model {i} predicts {} values, but model 0 predicts {}",
+				output.len(),
+				blended.len()
+			)));
+		}
+		for (blended, value) in blended.iter_mut().zip(output) {
+			*blended += weight(i) * value;
+		}
+	}
+	let total_weight: f64 = (0..models.len()).map(weight).sum();
+	blended.iter_mut().for_each(|value| *value /= total_weight);
+	Ok(blended)
+}
+
+/// Number of rows predicted at once by [`Booster::predict_to_writer`].
+const PREDICT_TO_WRITER_CHUNK_ROWS: usize = 4096;
+
+/// Parameters of the training of a model that [`Booster::refit_on`] refits it with
+const REFIT_PARAMS: [&str; 5] = [
+	"lambda_l1",
+	"lambda_l2",
+	"max_delta_step",
+	"path_smooth",
+	"refit_decay_rate",
+];
+
+/// Keys of the tree sections of a model string rounded by [`Booster::save_model_rounded`]
+const ROUNDED_MODEL_KEYS: [&str; 5] = [
+	"leaf_value",
+	"leaf_weight",
+	"split_gain",
+	"internal_value",
+	"internal_weight",
+];
+
+/// Round the values of [`ROUNDED_MODEL_KEYS`] in the trees of `model`, updating the
+/// `tree_sizes` header that LightGBM uses to find the trees.
+fn round_model_string(model: &str, decimals: usize) -> String {
+	let mut rounded = String::with_capacity(model.len());
+	let mut tree_sizes = Vec::new();
+	let mut tree_start: Option<usize> = None;
+	for line in model.split_inclusive('\n') {
+		let is_tree_start = line.starts_with("Tree=");
+		if is_tree_start || line.starts_with("end of trees") {
+			if let Some(start) = tree_start {
+				tree_sizes.push((rounded.len() - start).to_string());
+			}
+			tree_start = is_tree_start.then_some(rounded.len());
+		}
+		match line.split_once('=') {
+			Some((key, values)) if tree_start.is_some() && ROUNDED_MODEL_KEYS.contains(&key) => {
+				let values = values.trim_end_matches('\n');
+				rounded.push_str(key);
+				rounded.push('=');
+				for (i, value) in values.split(' ').enumerate() {
+					if i > 0 {
+						rounded.push(' ');
+					}
+					rounded.push_str(&round_literal(value, decimals));
+				}
+				if line.ends_with('\n') {
+					rounded.push('\n');
+				}
+			}
+			_ => rounded.push_str(line),
+		}
+	}
+
+	if let Some(line_start) = rounded.find("\ntree_sizes=").map(|i| i + 1) {
+		let line_end = rounded[line_start..]
+			.find('\n')
+			.map_or(rounded.len(), |i| line_start + i);
+		rounded.replace_range(
+			line_start..line_end,
+			&format!("tree_sizes={}", tree_sizes.join(" ")),
+		);
+	}
+	rounded
+}
+
+/// Round a floating point literal to `decimals` decimal places, leaving anything else as is.
+fn round_literal(literal: &str, decimals: usize) -> String {
+	match literal.parse::<f64>() {
+		Ok(value) if literal.contains(['.', 'e', 'E']) && value.is_finite() => {
+			let rounded = format!("{value:.decimals$}");
+			if rounded.contains('.') {
+				rounded
+					.trim_end_matches('0')
+					.trim_end_matches('.')
+					.to_owned()
+			} else {
+				rounded
+			}
+		}
+		_ => literal.to_owned(),
+	}
+}
+
+/// `n_rows * row_len`, or an error if that overflows.
+fn checked_len(n_rows: usize, row_len: usize) -> Result<usize> {
+	n_rows.checked_mul(row_len).ok_or_else(|| {
+		Error::conversion(format!(
+			"n_rows ({n_rows}) * row length ({row_len}) doesn't fit into an usize"
+		))
+	})
+}
+
+/// Check that `data` holds `n_rows` rows of `n_features` values.
+fn check_data_len(data: &[f64], n_rows: usize, n_features: usize) -> Result<()> {
+	if data.len() != checked_len(n_rows, n_features)? {
+		return Err(Error::invalid_input(format!(
+			"data len ({}) is not n_rows ({n_rows}) * n_features ({n_features})",
+			data.len(),
+		)));
+	}
+	Ok(())
+}
+
+/// Path of a row through a tree, see [`Booster::decision_path`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreePath {
+	/// Splits the row went through, from the root of the tree
+	pub nodes: Vec<PathNode>,
+	/// Index of the leaf the row ended up in
+	pub leaf_index: i32,
+	/// Value of that leaf, which the tree contributes to the raw score
+	pub leaf_value: f64,
+}
+
+/// Split a row went through, see [`Booster::decision_path`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathNode {
+	/// Index of the feature the split is on
+	pub feature_index: usize,
+	/// Rows with a feature value `<= threshold` go left. NaN for categorical splits.
+	pub threshold: f64,
+	/// Categories that go left, for categorical splits. Empty for numerical splits.
+	pub categories: Vec<i32>,
+	/// Whether the row went to the left child
+	pub went_left: bool,
+}
+
+/// Decide which child of the split `node` of a JSON model dump `row` goes to.
+fn route(node: &Value, row: &[f64]) -> Result<PathNode> {
+	let unexpected_format = || Error::ffi("unexpected split format in model dump");
+	let feature_index = node["split_feature"]
+		.as_u64()
+		.and_then(|feature| usize::try_from(feature).ok())
+		.filter(|&feature| feature < row.len())
+		.ok_or_else(unexpected_format)?;
+	let value = row[feature_index];
+	if node["decision_type"] == "==" {
+		let categories = node["threshold"]
+			.as_str()
+			.ok_or_else(unexpected_format)?
+			.split("||")
+			.map(|category| category.parse().map_err(|_| unexpected_format()))
+			.collect::<Result<Vec<i32>>>()?;
+		let went_left = !value.is_nan() && value >= 0. && categories.contains(&(value as i32));
+		return Ok(PathNode {
+			feature_index,
+			threshold: f64::NAN,
+			categories,
+			went_left,
+		});
+	}
+
+	let threshold = node["threshold"].as_f64().ok_or_else(unexpected_format)?;
+	let default_left = node["default_left"].as_bool().unwrap_or(true);
+	let missing_type = node["missing_type"].as_str().unwrap_or("None");
+	// NaN is only a missing value if the split handles it as such, otherwise it's zero
+	let value = if value.is_nan() && missing_type != "NaN" {
+		0.
+	} else {
+		value
+	};
+	let is_missing = match missing_type {
+		"Zero" => value.abs() <= 1e-35,
+		"NaN" => value.is_nan(),
+		_ => false,
+	};
+	Ok(PathNode {
+		feature_index,
+		threshold,
+		categories: Vec::new(),
+		went_left: if is_missing {
+			default_left
+		} else {
+			value <= threshold
+		},
+	})
+}
+
+/// Append the nodes of the tree rooted at `node` of a JSON model dump to `nodes`, in
+/// pre-order, returning the index of `node`.
+fn flatten_tree(node: &Value, nodes: &mut Vec<TreeNode>) -> Result<usize> {
+	let unexpected_format = || Error::ffi("unexpected node format in model dump");
+	let index = nodes.len();
+	if node.get("split_index").is_none() {
+		let value = node["leaf_value"].as_f64().ok_or_else(unexpected_format)?;
+		nodes.push(TreeNode::Leaf { value });
+		return Ok(index);
+	}
+
+	let feature = node["split_feature"]
+		.as_u64()
+		.and_then(|feature| usize::try_from(feature).ok())
+		.ok_or_else(unexpected_format)?;
+	let (threshold, categories) = if node["decision_type"] == "==" {
+		let categories = node["threshold"]
+			.as_str()
+			.ok_or_else(unexpected_format)?
+			.split("||")
+			.map(|category| category.parse().map_err(|_| unexpected_format()))
+			.collect::<Result<Vec<i32>>>()?;
+		(f64::NAN, categories)
+	} else {
+		let threshold = node["threshold"].as_f64().ok_or_else(unexpected_format)?;
+		(threshold, Vec::new())
+	};
+	let missing_type = match node["missing_type"].as_str() {
+		Some("Zero") => MissingType::Zero,
+		Some("NaN") => MissingType::NaN,
+		_ => MissingType::None,
+	};
+	// children are filled in once their index is known
+	nodes.push(TreeNode::Leaf { value: f64::NAN });
+	let left = flatten_tree(&node["left_child"], nodes)?;
+	let right = flatten_tree(&node["right_child"], nodes)?;
+	nodes[index] = TreeNode::Split {
+		feature,
+		threshold,
+		categories,
+		default_left: node["default_left"].as_bool().unwrap_or(true),
+		missing_type,
+		left,
+		right,
+	};
+	Ok(index)
+}
+
+/// Tree of a model, see [`Booster::export_trees`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tree {
+	/// Nodes of the tree, the first one being the root. Nodes refer to their children by
+	/// their index in this list.
+	pub nodes: Vec<TreeNode>,
+}
+
+/// Node of a [`Tree`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TreeNode {
+	/// Split of the rows between two children
+	Split {
+		/// Index of the feature the split is on
+		feature: usize,
+		/// Rows with a feature value `<= threshold` go left. NaN for categorical splits.
+		threshold: f64,
+		/// Categories that go left, for categorical splits. Empty for numerical splits.
+		categories: Vec<i32>,
+		/// Whether missing values go left
+		default_left: bool,
+		/// Which values are missing values
+		missing_type: MissingType,
+		/// Index of the left child
+		left: usize,
+		/// Index of the right child
+		right: usize,
+	},
+	/// Leaf, contributing its value to the raw score
+	Leaf {
+		/// Value of the leaf
+		value: f64,
+	},
+}
+
+/// How LightGBM handles a feature, see [`Booster::feature_types`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeatureType {
+	/// Split on thresholds
+	Numerical,
+	/// Split on sets of categories, see [`TreeNode::Split`]
+	Categorical,
+}
+
+/// Kind of task a model solves, see [`Booster::task`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Task {
+	/// Classification objectives: `binary`, `multiclass`, `multiclassova`, and the
+	/// cross-entropy objectives for probability labels. `num_class` is as returned by
+	/// [`Booster::num_classes`], so 1 for binary classification.
+	Classification { num_class: i32 },
+	/// Regression objectives, e.g. `regression`, `huber`, `poisson` or `quantile`
+	Regression,
+	/// Ranking objectives: `lambdarank` and `rank_xendcg`
+	Ranking,
+}
+
+/// Values that a split handles as missing, see [`TreeNode::Split`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissingType {
+	/// No missing values, NaN is handled as zero
+	None,
+	/// Zero is a missing value, NaN is handled as zero
+	Zero,
+	/// NaN is a missing value
+	NaN,
+}
+
+/// Size of the trees of a model, see [`Booster::tree_stats`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TreeStats {
+	/// Number of trees
+	pub num_trees: usize,
+	/// Number of leaves, summed over all trees
+	pub total_leaves: usize,
+	/// Average depth of the trees
+	pub avg_depth: f64,
+	/// Depth of the deepest tree
+	pub max_depth: usize,
+}
+
+/// Values of the metrics of a model on a dataset, keyed by metric name, see
+/// [`Booster::eval_results`].
+///
+/// Metric names are those reported by LightGBM, e.g. `auc`, `binary_logloss` or `ndcg@5`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EvalResults {
+	results: HashMap<String, f64>,
+}
+
+impl EvalResults {
+	/// Value of the metric `metric`, if it was computed.
+	pub fn get(&self, metric: &str) -> Option<f64> {
+		self.results.get(metric).copied()
+	}
+
+	/// Value of the AUC, if it was computed.
+	pub fn auc(&self) -> Option<f64> {
+		self.get("auc")
+	}
+
+	/// Iterate over the metric names and values, in arbitrary order.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+		self.results
+			.iter()
+			.map(|(name, &value)| (name.as_str(), value))
+	}
+}
+
+impl FromIterator<(String, f64)> for EvalResults {
+	fn from_iter<I: IntoIterator<Item = (String, f64)>>(iter: I) -> Self {
+		Self {
+			results: iter.into_iter().collect(),
+		}
+	}
+}
+
+/// Feature contributions (SHAP values) of a row, see [`Booster::predict_contrib`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContribRow {
+	/// Contribution of each feature, in the order of the features of the model
+	pub contributions: Vec<f64>,
+	/// Expected value of the model, i.e. its average raw score on the training data, from
+	/// which the contributions depart
+	pub base: f64,
+}
+
+/// Differences between the predictions of two models, see [`Booster::prediction_diff`].
+///
+/// For multiclass models, each class probability of each row counts as a prediction.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PredictionDiff {
+	/// Largest absolute difference between two predictions
+	pub max_abs: f64,
+	/// Mean absolute difference between predictions
+	pub mean_abs: f64,
+	/// Number of predictions that differ
+	pub num_changed: usize,
+}
+
+/// Most likely class of a row, see [`Booster::predict_top_class`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClassPrediction {
+	/// Index of the class
+	pub class: usize,
+	/// Predicted probability of the class
+	pub probability: f64,
+}
+
+/// Importance of a feature in a model, see [`Booster::feature_importances`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeatureImportance {
+	/// Index of the feature
+	pub index: usize,
+	/// Name of the feature
+	pub name: String,
+	/// Number of times the feature is used to split
+	pub split_count: u64,
+	/// Total gain of the splits on the feature
+	pub total_gain: f64,
+}
+
+/// Type of prediction, see [`Booster::predict_with_type`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PredictType {
+	/// Predicted values, transformed by the objective (e.g. probabilities for binary
+	/// classification). One value per row and class.
+	Normal,
+	/// Raw scores, before the objective's transformation. One value per row and class.
+	RawScore,
+	/// Index of the leaf reached in each tree. One value per row and tree.
+	LeafIndex,
+	/// Feature contributions (SHAP values). `num_feature() + 1` values per row and class, the
+	/// last one being the expected value of the model. Prefer
+	/// [`Booster::predict_contrib`], which separates that value from the contributions.
+	Contrib,
+}
+
+impl PredictType {
+	pub(crate) fn as_c_api(self) -> i32 {
+		match self {
+			PredictType::Normal => lightgbm_sys::C_API_PREDICT_NORMAL,
+			PredictType::RawScore => lightgbm_sys::C_API_PREDICT_RAW_SCORE,
+			PredictType::LeafIndex => lightgbm_sys::C_API_PREDICT_LEAF_INDEX,
+			PredictType::Contrib => lightgbm_sys::C_API_PREDICT_CONTRIB,
+		}
+	}
+}
+
+/// Type of feature importance, see [`Booster::feature_importance_report`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportanceType {
+	/// Number of times the feature is used to split
+	Split,
+	/// Total gain of the splits on the feature
+	Gain,
+}
+
+impl ImportanceType {
+	fn as_c_api(self) -> i32 {
+		match self {
+			ImportanceType::Split => lightgbm_sys::C_API_FEATURE_IMPORTANCE_SPLIT,
+			ImportanceType::Gain => lightgbm_sys::C_API_FEATURE_IMPORTANCE_GAIN,
+		}
+	}
+}
+
+type StringListGetter = unsafe extern "C" fn(
+	lightgbm_sys::BoosterHandle,
+	i32,
+	*mut i32,
+	usize,
+	*mut usize,
+	*mut *mut c_char,
+) -> i32;
+
+struct StringList {
+	strings: Vec<Vec<u8>>,
+	actual_string_len: usize,
+	num_strings: i32,
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		serde_json::json,
+		std::{fs, path::Path},
+	};
+
+	fn _read_train_file() -> Result<Dataset> {
+		Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+	}
+
+	fn _train_booster(params: &Value) -> Booster {
+		let dataset = _read_train_file().unwrap();
+		Booster::train(dataset, params).unwrap()
+	}
+
+	fn _default_params() -> Value {
+		// no trees, which tests such as `dump_model_json` rely on
+		let params = json! {
+			{
+				"num_iterations": 0,
+				"objective": "binary",
+				"metric": "auc",
+				"data_random_seed": 0
+			}
+		};
+		params
+	}
+
+	#[test]
+	fn predict() {
+		let params = json! {
+			{
+				"num_iterations": 10,
+				"objective": "binary",
+				"metric": "auc",
+				"data_random_seed": 0
+			}
+		};
+		let mut bst = _train_booster(&params);
+		let features = [[0.5; 28], [0.0; 28], [0.9; 28]]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		let result = bst.predict(&features).unwrap();
+		let mut normalized_result: Vec<i32> = Vec::new();
+		for &r in &result {
+			normalized_result.push((r > 0.5).into());
+		}
+		assert_eq!(normalized_result, vec![0, 0, 1]);
+	}
+
+	#[test]
+	fn predict_single_row() {
+		let params = json! {
+			{
+				"num_iterations": 10,
+				"objective": "binary",
+				"metric": "auc",
+				"data_random_seed": 0
+			}
+		};
+		let bst = _train_booster(&params);
+		let feature = [[0.5; 28], [0.0; 28], [0.9; 28]];
+
+		let result: Vec<f64> = feature
+			.iter()
+			.flat_map(|f| bst.predict_single_row(f).unwrap())
+			.collect();
+
+		let mut normalized_result: Vec<i32> = Vec::new();
+		for r in result {
+			normalized_result.push((r > 0.5).into());
+		}
+		assert_eq!(normalized_result, vec![0, 0, 1]);
+	}
+
+	#[test]
 	fn predict_single_row_fast() {
 		let params = json! {
 			{
 				"num_iterations": 10,
 				"objective": "binary",
-				"metric": "auc",
+				"metric": "auc",
+				"data_random_seed": 0
+			}
+		};
+		let bst = _train_booster(&params);
+		let single_row_predictor = bst.single_row_predictor().unwrap();
+
+		let feature = [[0.5; 28], [0.0; 28], [0.9; 28]];
+
+		let result: Vec<f64> = feature
+			.iter()
+			.flat_map(|f| single_row_predictor.predict(f).unwrap())
+			.collect();
+
+		let mut normalized_result: Vec<i32> = Vec::new();
+		for r in result {
+			normalized_result.push((r > 0.5).into());
+		}
+		assert_eq!(normalized_result, vec![0, 0, 1]);
+	}
+
+	#[test]
+	fn predict_rows_iter() {
+		let params = json! {
+			{
+				"num_iterations": 10,
+				"objective": "binary",
+				"metric": "auc",
+				"data_random_seed": 0
+			}
+		};
+		let mut bst = _train_booster(&params);
+		let features = [[0.5; 28], [0.0; 28], [0.9; 28]]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		let rows = bst
+			.predict_rows_iter(&features, 3)
+			.collect::<Result<Vec<_>>>()
+			.unwrap();
+		assert_eq!(rows.len(), 3);
+		assert_eq!(rows.concat(), bst.predict(&features).unwrap());
+		assert!(bst
+			.predict_rows_iter(&features, 2)
+			.collect::<Result<Vec<_>>>()
+			.is_err());
+	}
+
+	#[test]
+	fn predict_wrong_shape() {
+		let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		let err = bst
+			.predict_with_type(&[0.5; 27], 1, PredictType::Normal)
+			.unwrap_err();
+		assert_eq!(err.kind(), crate::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn warmup() {
+		let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		assert_eq!(bst.warmup(28), Ok(()));
+		assert!(bst.warmup(27).is_err());
+	}
+
+	#[test]
+	fn prediction_diff() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(3);
+		let bst = _train_booster(&params);
+		let data = [0.5; 3 * 28];
+		assert_eq!(
+			bst.prediction_diff(&bst, &data, 3).unwrap(),
+			PredictionDiff::default()
+		);
+		let other = Booster::from_file("./test/test_from_file.input").unwrap();
+		assert!(bst.prediction_diff(&other, &data, 3).is_ok());
+		let mut params = _default_params();
+		params["num_class"] = json!(3);
+		params["objective"] = json!("multiclass");
+		let multiclass = _train_booster(&params);
+		assert!(bst.prediction_diff(&multiclass, &data, 3).is_err());
+	}
+
+	#[test]
+	fn predict_to_writer() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(2);
+		let bst = _train_booster(&params);
+		let n_rows = PREDICT_TO_WRITER_CHUNK_ROWS + 3;
+		let data = (0..n_rows * 28)
+			.map(|i| (i % 37) as f64 / 10.)
+			.collect::<Vec<_>>();
+		for predict_type in [PredictType::Normal, PredictType::LeafIndex] {
+			let mut written = Vec::new();
+			bst.predict_to_writer(&data, n_rows, predict_type, &mut written)
+				.unwrap();
+			let parsed = String::from_utf8(written)
+				.unwrap()
+				.lines()
+				.flat_map(|line| {
+					line.split('\t')
+						.map(|value| value.parse::<f64>().unwrap())
+						.collect::<Vec<_>>()
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(
+				parsed,
+				bst.predict_with_type(&data, n_rows, predict_type).unwrap()
+			);
+		}
+		assert!(bst
+			.predict_to_writer(&data[1..], n_rows, PredictType::Normal, &mut Vec::new())
+			.is_err());
+		assert!(bst
+			.predict_to_writer(&[], 5, PredictType::Normal, &mut Vec::new())
+			.is_err());
+		assert!(bst
+			.predict_to_writer(&data[..5 * 27], 5, PredictType::Normal, &mut Vec::new())
+			.is_err());
+
+		// a model without trees has no leaf indices
+		let empty = Booster::from_file("./test/test_from_file.input").unwrap();
+		let mut written = Vec::new();
+		empty
+			.predict_to_writer(&data[..3 * 28], 3, PredictType::LeafIndex, &mut written)
+			.unwrap();
+		assert_eq!(written, b"\n\n\n");
+	}
+
+	#[test]
+	fn predict_checked() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(2);
+		let bst = _train_booster(&params);
+		let data = [0.5; 2 * 28];
+		assert_eq!(
+			bst.predict_checked(&data, 2),
+			bst.predict_with_type(&data, 2, PredictType::Normal)
+		);
+
+		// break the model by setting its leaf values to NaN, removing tree sizes as they
+		// no longer match
+		let model = String::from_utf8(bst.save_string().unwrap()).unwrap();
+		let broken_model = model
+			.lines()
+			.filter(|line| !line.starts_with("tree_sizes="))
+			.map(|line| match line.strip_prefix("leaf_value=") {
+				Some(values) => format!(
+					"leaf_value={}",
+					vec!["nan"; values.split(' ').count()].join(" ")
+				),
+				None => line.to_owned(),
+			})
+			.collect::<Vec<_>>()
+			.join("\n");
+		let broken_bst = Booster::from_string(broken_model.as_bytes()).unwrap();
+		let error = broken_bst.predict_checked(&data, 2).unwrap_err();
+		assert!(error.to_string().contains("row 0"));
+	}
+
+	#[test]
+	fn predict_raw_and_proba() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(3);
+		let bst = _train_booster(&params);
+		let data = [0.5; 2 * 28];
+		let (raw, proba) = bst.predict_raw_and_proba(&data, 2).unwrap();
+		assert_eq!(raw.len(), 2);
+		for (raw, proba) in raw.iter().zip(&proba) {
+			assert!((1. / (1. + (-raw).exp()) - proba).abs() < 1e-9);
+		}
+		let normal = bst
+			.predict_with_type(&data, 2, PredictType::Normal)
+			.unwrap();
+		for (proba, normal) in proba.iter().zip(&normal) {
+			assert!((proba - normal).abs() < 1e-9);
+		}
+	}
+
+	#[test]
+	fn predict_response() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(3);
+		params["objective"] = json!("poisson");
+		params["metric"] = json!("poisson");
+		let bst = _train_booster(&params);
+		let data = [0.5; 2 * 28];
+		let response = bst.predict_response(&data, 2).unwrap();
+		let raw = bst
+			.predict_with_type(&data, 2, PredictType::RawScore)
+			.unwrap();
+		assert_eq!(response.len(), 2);
+		for (response, raw) in response.iter().zip(&raw) {
+			assert!((response - raw.exp()).abs() < 1e-9);
+		}
+
+		let binary_bst = _train_booster(&_default_params());
+		assert!(binary_bst.predict_response(&data, 2).is_err());
+	}
+
+	#[test]
+	fn predict_with_config() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(20);
+		let mut bst = _train_booster(&params);
+		let data = (0..10 * 28)
+			.map(|i| (i % 13) as f64 / 13.)
+			.collect::<Vec<_>>();
+		let full = bst
+			.predict_with_type(&data, 10, PredictType::Normal)
+			.unwrap();
+		let never_stop = PredictConfig::new(PredictType::Normal).early_stopping(5, 1e9);
+		assert_eq!(
+			bst.predict_with_config(&data, 10, &never_stop),
+			Ok(full.clone())
+		);
+
+		// with a zero margin, prediction stops after the first tree
+		let stop_at_once = PredictConfig::new(PredictType::Normal).early_stopping(1, 0.);
+		let early = bst.predict_with_config(&data, 10, &stop_at_once).unwrap();
+		assert_eq!(early.len(), full.len());
+		assert!(early.iter().all(|p| (0.0..=1.0).contains(p)));
+		bst.truncate(1).unwrap();
+		let first_tree = bst
+			.predict_with_type(&data, 10, PredictType::Normal)
+			.unwrap();
+		for (early, first_tree) in early.iter().zip(&first_tree) {
+			assert!((early - first_tree).abs() < 1e-12);
+		}
+
+		let invalid = PredictConfig::new(PredictType::Normal).early_stopping(0, 1.);
+		assert!(bst.predict_with_config(&data, 10, &invalid).is_err());
+	}
+
+	#[test]
+	fn predict_clamped() {
+		// the label grows with feature 0, so clamping it changes predictions
+		let n_rows = 200;
+		let data: Vec<f64> = (0..n_rows)
+			.flat_map(|i| [(i % 10 + 1) as f64, (i * 7 % 13) as f64])
+			.collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 10) as f32).collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.objective(crate::Objective::Regression)
+			.min_data_in_leaf(5)
+			.set("num_iterations", 10);
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+		let min = [1., 0.];
+		let max = [3., 12.];
+		let row = [10., 5.];
+		let in_bounds_row = [2., 5.];
+		let clamped_to_max = [3., 5.];
+
+		let unclamped = bst.predict_with_type(&row, 1, PredictType::Normal).unwrap();
+		let clamped = bst
+			.predict_with_type(&clamped_to_max, 1, PredictType::Normal)
+			.unwrap();
+		assert_ne!(unclamped, clamped);
+		assert_eq!(bst.predict_clamped(&row, 1, &min, &max).unwrap(), clamped);
+		assert_eq!(
+			bst.predict_clamped(&in_bounds_row, 1, &min, &max).unwrap(),
+			bst.predict_with_type(&in_bounds_row, 1, PredictType::Normal)
+				.unwrap()
+		);
+		assert!(bst.predict_clamped(&row, 1, &min[1..], &max).is_err());
+		assert!(bst.predict_clamped(&row, 1, &max, &min).is_err());
+	}
+
+	#[test]
+	fn refit_on() {
+		let n_rows = 200;
+		let data: Vec<f64> = (0..n_rows)
+			.flat_map(|i| [(i % 10) as f64, (i * 7 % 13) as f64])
+			.collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 10) as f32).collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.objective(crate::Objective::Regression)
+			.min_data_in_leaf(5)
+			.set("num_iterations", 10);
+		let mut bst = Booster::train_with_params(dataset, &params).unwrap();
+
+		let shifted_label: Vec<f32> = label.iter().map(|l| l + 5.).collect();
+		let error = |bst: &Booster| {
+			let predictions = bst
+				.predict_with_type(&data, n_rows, PredictType::Normal)
+				.unwrap();
+			predictions
+				.iter()
+				.zip(&shifted_label)
+				.map(|(&p, &l)| (p - l as f64).abs())
+				.sum::<f64>()
+		};
+		let error_before = error(&bst);
+		let leaves_before = bst.predict_leaf_indices(&data, n_rows).unwrap();
+		let num_trees = bst.total_num_trees().unwrap();
+
+		assert_eq!(bst.refit_on(&data, n_rows, &shifted_label), Ok(()));
+		assert!(error(&bst) < error_before);
+		assert_eq!(bst.total_num_trees(), Ok(num_trees));
+		assert_eq!(bst.predict_leaf_indices(&data, n_rows), Ok(leaves_before));
+
+		assert!(bst.refit_on(&data, n_rows, &shifted_label[1..]).is_err());
+		assert!(bst.refit_on(&[], 0, &[]).is_err());
+		// with a decay rate of 1, refitted leaves keep their old values
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = params.set("refit_decay_rate", 1).set("lambda_l2", 10);
+		let mut bst = Booster::train_with_params(dataset, &params).unwrap();
+		let predictions_before = bst
+			.predict_with_type(&data, n_rows, PredictType::Normal)
+			.unwrap();
+		bst.refit_on(&data, n_rows, &shifted_label).unwrap();
+		let training_params = bst.training_params().unwrap();
+		assert_eq!(
+			training_params.get("lambda_l2").map(String::as_str),
+			Some("10")
+		);
+		assert_eq!(
+			bst.predict_with_type(&data, n_rows, PredictType::Normal),
+			Ok(predictions_before)
+		);
+	}
+
+	#[test]
+	fn predict_masked() {
+		let n_rows = 200;
+		let data: Vec<f64> = (0..n_rows)
+			.flat_map(|i| [(i % 10 + 1) as f64, (i * 7 % 13) as f64])
+			.collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 10) as f32).collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.objective(crate::Objective::Regression)
+			.min_data_in_leaf(5)
+			.set("num_iterations", 10);
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+
+		let rows = [10., 3., 2., 5.];
+		let full = bst
+			.predict_with_type(&rows, 2, PredictType::Normal)
+			.unwrap();
+		let masked = bst.predict_masked(&rows, 2, &[0]).unwrap();
+		let missing = bst
+			.predict_with_type(&[f64::NAN, 3., f64::NAN, 5.], 2, PredictType::Normal)
+			.unwrap();
+		assert_eq!(masked, missing);
+		assert_ne!(masked[0], full[0]);
+		assert_eq!(bst.predict_masked(&rows, 2, &[]), Ok(full));
+		assert!(bst.predict_masked(&rows, 2, &[2]).is_err());
+		assert!(bst.predict_masked(&rows[1..], 2, &[0]).is_err());
+	}
+
+	#[test]
+	fn predict_too_many_rows() {
+		let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		// 28 features, so with wrapping arithmetic this many rows would take 0 values
+		let n_rows = 1 << (usize::BITS - 1);
+		let error = bst
+			.predict_with_type(&[], n_rows, PredictType::Normal)
+			.unwrap_err();
+		assert_eq!(error.kind(), crate::ErrorKind::Conversion);
+		let error = bst
+			.predict_rows_iter(&[], n_rows)
+			.next()
+			.unwrap()
+			.unwrap_err();
+		assert_eq!(error.kind(), crate::ErrorKind::Conversion);
+	}
+
+	#[test]
+	fn predict_leaf_indices() {
+		let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		let data = [0.5; 2 * 28];
+		let leaf_indices = bst.predict_leaf_indices(&data, 2).unwrap();
+		let total_num_trees = bst.total_num_trees().unwrap();
+		assert_eq!(leaf_indices.len(), 2);
+		assert!(leaf_indices.iter().all(|row| row.len() == total_num_trees));
+		assert_eq!(leaf_indices[0], leaf_indices[1]);
+	}
+
+	#[cfg(feature = "dataframe")]
+	#[test]
+	fn predict_dataframe() {
+		use polars::df;
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let data = data.iter().flatten().copied().collect::<Vec<_>>();
+		let dataset = Dataset::from_mat_named(
+			&data,
+			5,
+			&[0.0, 0.0, 0.0, 1.0, 1.0],
+			&["feature_1", "feature_2", "feature_3", "feature_4"],
+		)
+		.unwrap();
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.set("objective", "binary")
+			.set("min_data_in_leaf", 1);
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+
+		// columns in a different order than training, with an unused one
+		let df: DataFrame = df![
+			"feature_4" => [0.1, 0.1, 0.1, 0.7, 0.9],
+			"unused" => [5.0, 5.0, 5.0, 5.0, 5.0],
+			"feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7],
+			"feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
+			"feature_3" => [0.2, 0.5, 0.5, 0.8, 1.0]
+		]
+		.unwrap();
+		assert_eq!(
+			bst.predict_dataframe(&df, PredictType::Normal),
+			bst.predict_with_type(&data, 5, PredictType::Normal)
+		);
+
+		let df: DataFrame = df!["feature_1" => [1.0, 0.7]].unwrap();
+		assert!(bst.predict_dataframe(&df, PredictType::Normal).is_err());
+	}
+
+	#[test]
+	fn shap_values() {
+		let params = json! {
+			{
+				"num_iterations": 10,
+				"objective": "binary",
+				"metric": "auc",
+				"data_random_seed": 0
+			}
+		};
+		let bst = _train_booster(&params);
+		let features = [[0.5; 28], [0.0; 28], [0.9; 28]]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		let (shap_values, bias) = bst.shap_values(&features, 3).unwrap();
+		let raw_scores = bst
+			.predict_with_type(&features, 3, PredictType::RawScore)
+			.unwrap();
+		assert_eq!(shap_values.len(), 3);
+		assert_eq!(bias.len(), 3);
+		for ((row, bias), raw_score) in shap_values.iter().zip(bias).zip(raw_scores) {
+			assert_eq!(row.len(), 28);
+			assert!((row.iter().sum::<f64>() + bias - raw_score).abs() < 1e-9);
+		}
+	}
+
+	#[test]
+	fn predict_contrib() {
+		// feature 0 is constant, feature 1 determines the label
+		let n_rows = 200;
+		let data: Vec<f64> = (0..n_rows).flat_map(|i| [1., (i % 10) as f64]).collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 10) as f32 * 2.).collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.objective(crate::Objective::Regression)
+			.min_data_in_leaf(5)
+			.set("num_iterations", 10);
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+
+		let rows = [1., 0., 1., 9., 1., 4.];
+		let contrib = bst.predict_contrib(&rows, 3).unwrap();
+		let raw_scores = bst
+			.predict_with_type(&rows, 3, PredictType::RawScore)
+			.unwrap();
+		let mean_label = label.iter().map(|&l| l as f64).sum::<f64>() / n_rows as f64;
+		assert_eq!(contrib.len(), 3);
+		for (row, raw_score) in contrib.iter().zip(raw_scores) {
+			assert_eq!(row.contributions.len(), 2);
+			assert_eq!(row.contributions[0], 0.);
+			assert!((row.base - mean_label).abs() < 1e-6);
+			assert!((row.contributions.iter().sum::<f64>() + row.base - raw_score).abs() < 1e-9);
+		}
+		assert!(contrib[0].contributions[1] < 0.);
+		assert!(contrib[1].contributions[1] > 0.);
+	}
+
+	#[cfg(feature = "ndarray")]
+	#[test]
+	fn predict_to_array2() {
+		let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		let features = [[0.5; 28], [0.0; 28], [0.9; 28]]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		let result = bst.predict_to_array2(&features, 3).unwrap();
+		assert_eq!(result.shape(), &[3, 1]);
+		assert_eq!(
+			result.into_raw_vec(),
+			bst.predict_with_type(&features, 3, PredictType::Normal)
+				.unwrap()
+		);
+	}
+
+	#[test]
+	fn train_force_col_wise() {
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.set("objective", "binary")
+			.force_col_wise(true);
+		let bst = Booster::train_with_params(_read_train_file().unwrap(), &params).unwrap();
+		assert_eq!(bst.num_feature(), Ok(28));
+	}
+
+	#[test]
+	fn train_linear_tree() {
+		let data = [
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		]
+		.into_iter()
+		.flatten()
+		.collect::<Vec<_>>();
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.set("objective", "regression")
+			.set("min_data_in_leaf", 1)
+			.linear_tree(true);
+		let dataset = Dataset::from_mat_with_params(&data, 5, label, &params).unwrap();
+		let mut bst = Booster::train_with_params(dataset, &params).unwrap();
+		let result = bst.predict(&data).unwrap();
+		assert_eq!(result.len(), 5);
+		assert!(result.iter().all(|r| r.is_finite()));
+	}
+
+	#[test]
+	fn train_min_data_in_leaf() {
+		let num_splits = |params: Params| {
+			let params = params
+				.set("num_iterations", 5)
+				.set("objective", "binary")
+				.set("data_random_seed", 0);
+			Booster::train_with_params(_read_train_file().unwrap(), &params)
+				.unwrap()
+				.feature_importance()
+				.unwrap()
+				.into_iter()
+				.sum::<f64>()
+		};
+		let default_splits = num_splits(Params::new());
+		let constrained_splits = num_splits(Params::new().min_data_in_leaf(2000));
+		assert!(constrained_splits < default_splits);
+	}
+
+	#[test]
+	fn train_multiclass_invalid_label() {
+		let data = [[1.0, 0.1], [0.7, 0.4], [0.9, 0.8]]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		let dataset = Dataset::from_mat(&data, 3, &[0.0, 1.0, 2.0]).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.objective(crate::Objective::MulticlassSoftmax { num_class: 2 });
+		assert_eq!(
+			Booster::train_with_params(dataset, &params).err(),
+			Some(Error::invalid_input(
+				"label 2 at row 2 is not a class index in 0..2"
+			))
+		);
+	}
+
+	#[test]
+	fn num_eval_metrics() {
+		let mut params = _default_params();
+		params["metric"] = json!("auc,binary_logloss");
+		let mut bst = _train_booster(&params);
+		let valid_data = Dataset::from_mat_with_reference(
+			&[0.5; 2 * 28],
+			2,
+			&[0., 1.],
+			bst.train_data.as_ref().unwrap(),
+		)
+		.unwrap();
+		bst.add_valid_data(valid_data).unwrap();
+		assert_eq!(bst.num_eval_metrics(), Ok(2));
+		assert_eq!(bst.eval(1).unwrap().len(), 2);
+	}
+
+	#[test]
+	fn eval_weighted_valid_data() {
+		let train_data = _read_train_file().unwrap();
+		let features = [[0.5; 28], [0.0; 28], [0.9; 28]]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		let label = &[0.0, 0.0, 1.0];
+		let valid_data =
+			Dataset::from_mat_with_reference(&features, 3, label, &train_data).unwrap();
+		let mut weighted_valid_data =
+			Dataset::from_mat_with_reference(&features, 3, label, &train_data).unwrap();
+		weighted_valid_data.set_weights(&[1.0, 1.0, 10.0]).unwrap();
+
+		let params = json! {
+			{
+				"num_iterations": 10,
+				"objective": "binary",
+				"metric": "binary_logloss",
 				"data_random_seed": 0
 			}
 		};
-		let bst = _train_booster(&params);
-		let single_row_predictor = bst.single_row_predictor().unwrap();
+		let mut bst = Booster::train(train_data, &params).unwrap();
+		bst.add_valid_data(valid_data).unwrap();
+		bst.add_valid_data(weighted_valid_data).unwrap();
+		assert_eq!(bst.eval_names(), Ok(vec!["binary_logloss".to_owned()]));
 
-		let feature = [[0.5; 28], [0.0; 28], [0.9; 28]];
+		let unweighted = bst.eval(1).unwrap();
+		let weighted = bst.eval(2).unwrap();
+		assert_eq!(unweighted.len(), 1);
+		assert_eq!(weighted.len(), 1);
+		assert_eq!(weighted[0].0, "binary_logloss");
+		assert_ne!(unweighted[0].1, weighted[0].1);
+	}
 
-		let result: Vec<f64> = feature
+	#[test]
+	fn eval_valid() {
+		let train_data = _read_train_file().unwrap();
+		let features = [[0.5; 28], [0.0; 28], [0.9; 28]]
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		let first_valid_data =
+			Dataset::from_mat_with_reference(&features, 3, &[0.0, 0.0, 1.0], &train_data).unwrap();
+		let second_valid_data =
+			Dataset::from_mat_with_reference(&features, 3, &[1.0, 1.0, 0.0], &train_data).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 10)
+			.set("objective", "binary")
+			.set("metric", "binary_logloss,auc")
+			.set("data_random_seed", 0);
+		let mut bst = Booster::train_with_params(train_data, &params).unwrap();
+		bst.add_valid_data(first_valid_data).unwrap();
+		bst.add_valid_data(second_valid_data).unwrap();
+
+		let train = bst.eval_valid(0).unwrap();
+		let first = bst.eval_valid(1).unwrap();
+		let second = bst.eval_valid(2).unwrap();
+		for metrics in [&train, &first, &second] {
+			let names = metrics
+				.iter()
+				.map(|(name, _)| name.as_str())
+				.collect::<Vec<_>>();
+			assert_eq!(names, ["binary_logloss", "auc"]);
+		}
+		assert_eq!(first, bst.eval(1).unwrap());
+		assert_ne!(first[0].1, second[0].1);
+		assert!(bst.eval_valid(3).is_err());
+
+		let loaded = Booster::from_file("./test/test_from_file.input").unwrap();
+		assert!(loaded.eval_valid(0).is_err());
+	}
+
+	#[test]
+	fn eval_results() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(5);
+		params["metric"] = json!("auc,binary_logloss");
+		let bst = _train_booster(&params);
+		let eval = bst.eval(0).unwrap();
+		let results = bst.eval_results(0).unwrap();
+		assert_eq!(results.get("auc"), Some(eval[0].1));
+		assert_eq!(results.auc(), Some(eval[0].1));
+		assert_eq!(results.get("binary_logloss"), Some(eval[1].1));
+		assert_eq!(results.get("l2"), None);
+		assert_eq!(results.iter().count(), 2);
+		assert!(bst.eval_results(1).is_err());
+	}
+
+	#[test]
+	fn reset_training_data() {
+		let n_rows = 100;
+		let data = (0..n_rows)
+			.map(|i| i as f64 / n_rows as f64)
+			.collect::<Vec<_>>();
+		let label = data
 			.iter()
-			.flat_map(|f| single_row_predictor.predict(f).unwrap())
-			.collect();
+			.map(|&x| (x > 0.5) as i32 as f32)
+			.collect::<Vec<_>>();
+		let flipped_label = label.iter().map(|&y| 1. - y).collect::<Vec<_>>();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let mut next_dataset =
+			Dataset::from_mat_with_reference(&data, n_rows, &label, &dataset).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 10)
+			.set("objective", "binary")
+			.set("min_data_in_leaf", 1);
+		let mut bst = Booster::train_with_params(dataset, &params).unwrap();
+		let predict = |bst: &Booster| {
+			bst.predict_with_type(&[0.9], 1, PredictType::Normal)
+				.unwrap()[0]
+		};
+		assert!(predict(&bst) > 0.5);
 
-		let mut normalized_result: Vec<i32> = Vec::new();
-		for r in result {
-			normalized_result.push((r > 0.5).into());
+		next_dataset.set_label(&flipped_label).unwrap();
+		let previous = bst.reset_training_data(next_dataset).unwrap();
+		assert_eq!(previous.get_label(), Ok(label));
+		for _ in 0..30 {
+			bst.update_one_iter().unwrap();
 		}
-		assert_eq!(normalized_result, vec![0, 0, 1]);
+		assert!(predict(&bst) < 0.5);
+
+		let mut dataset = Dataset::from_mat(&[0.1, 0.2], 2, &[0., 1.]).unwrap();
+		assert!(dataset.set_label(&[0.]).is_err());
+		let mut loaded = Booster::from_file("./test/test_from_file.input").unwrap();
+		assert!(loaded.update_one_iter().is_err());
+		assert!(loaded.reset_training_data(previous).is_err());
+	}
+
+	#[test]
+	fn add_valid_data_to_loaded_model() {
+		let mut bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		let data = [[0.5; 28]].into_iter().flatten().collect::<Vec<_>>();
+		let dataset = Dataset::from_mat(&data, 1, &[0.0]).unwrap();
+		assert!(bst.add_valid_data(dataset).is_err());
+	}
+
+	#[test]
+	fn train_feature_parallel() {
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.objective(crate::Objective::Binary)
+			.tree_learner(crate::TreeLearner::Feature);
+		assert_eq!(params.get("tree_learner"), Some("feature"));
+		let bst = Booster::train_with_params(_read_train_file().unwrap(), &params).unwrap();
+		let result = bst
+			.predict_with_type(&[0.5; 28], 1, PredictType::Normal)
+			.unwrap();
+		assert_eq!(result.len(), 1);
+		assert!((0.0..=1.0).contains(&result[0]));
 	}
 
 	#[test]
@@ -632,6 +3461,275 @@ mod tests {
 		assert_eq!(feature_importance, vec![0.0; 28]);
 	}
 
+	#[test]
+	fn permutation_importance() {
+		// feature 0 determines the label, feature 1 is noise
+		let mut rng = crate::dataset::SplitMix64(42);
+		let mut uniform = || (rng.next() >> 11) as f64 / (1_u64 << 53) as f64;
+		let n_rows = 500;
+		let data: Vec<f64> = (0..2 * n_rows).map(|_| uniform()).collect();
+		let labels: Vec<f32> = data
+			.chunks(2)
+			.map(|row| if row[0] > 0.5 { 1. } else { 0. })
+			.collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &labels).unwrap();
+		let params = json! {
+			{
+				"num_iterations": 10,
+				"objective": "binary",
+				"verbosity": -1,
+				"data_random_seed": 0
+			}
+		};
+		let bst = Booster::train(dataset, &params).unwrap();
+
+		let accuracy = |predictions: &[f64], labels: &[f32]| {
+			let correct = predictions
+				.iter()
+				.zip(labels)
+				.filter(|&(&p, &l)| (p > 0.5) == (l > 0.5))
+				.count();
+			correct as f64 / labels.len() as f64
+		};
+		let importances = bst
+			.permutation_importance(&data, n_rows, &labels, accuracy, 3, 0)
+			.unwrap();
+		assert_eq!(importances.len(), 2);
+		assert!(importances[0] > 0.3);
+		assert!(importances[0] > importances[1]);
+		assert_eq!(
+			bst.permutation_importance(&data, n_rows, &labels, accuracy, 3, 0),
+			Ok(importances)
+		);
+
+		assert!(bst
+			.permutation_importance(&data, n_rows, &labels, accuracy, 0, 0)
+			.is_err());
+		assert!(bst
+			.permutation_importance(&data, n_rows, &labels[1..], accuracy, 1, 0)
+			.is_err());
+	}
+
+	#[test]
+	fn feature_importance_report() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(5);
+		let bst = _train_booster(&params);
+		let report = bst
+			.feature_importance_report(ImportanceType::Gain, 0)
+			.unwrap();
+		assert_eq!(report.len(), 28);
+		assert!(report.windows(2).all(|w| w[0].1 >= w[1].1));
+		assert!(report[0].1 > 0.);
+		let feature_names = bst.feature_names().unwrap();
+		assert!(report.iter().all(|(name, _)| feature_names.contains(name)));
+
+		let split_report = bst
+			.feature_importance_report(ImportanceType::Split, 0)
+			.unwrap();
+		let feature_importance = bst.feature_importance().unwrap();
+		let index = feature_names
+			.iter()
+			.position(|name| *name == split_report[0].0);
+		assert_eq!(feature_importance[index.unwrap()], split_report[0].1);
+	}
+
+	#[test]
+	fn export_trees() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(3);
+		params["num_leaves"] = json!(8);
+		let bst = _train_booster(&params);
+		let trees = bst.export_trees().unwrap();
+		assert_eq!(trees.len(), bst.total_num_trees().unwrap());
+		match &trees[0].nodes[0] {
+			TreeNode::Split {
+				feature,
+				threshold,
+				left,
+				right,
+				..
+			} => {
+				assert!(*feature < 28);
+				assert!(threshold.is_finite());
+				assert!(*left < trees[0].nodes.len() && *right < trees[0].nodes.len());
+			}
+			TreeNode::Leaf { .. } => panic!("root of the first tree is a leaf"),
+		}
+		let n_leaves = trees[0]
+			.nodes
+			.iter()
+			.filter(|node| matches!(node, TreeNode::Leaf { .. }))
+			.count();
+		assert_eq!(n_leaves, bst.leaf_counts(0).unwrap().len());
+
+		// walking the exported trees gives the raw score
+		let row = [0.5; 28];
+		let raw_score: f64 = trees
+			.iter()
+			.map(|tree| {
+				let mut node = &tree.nodes[0];
+				loop {
+					match node {
+						TreeNode::Split {
+							feature,
+							threshold,
+							left,
+							right,
+							..
+						} => {
+							node = &tree.nodes[if row[*feature] <= *threshold {
+								*left
+							} else {
+								*right
+							}]
+						}
+						TreeNode::Leaf { value } => return *value,
+					}
+				}
+			})
+			.sum();
+		let expected = bst
+			.predict_with_type(&row, 1, PredictType::RawScore)
+			.unwrap();
+		assert!((raw_score - expected[0]).abs() < 1e-9);
+	}
+
+	#[test]
+	fn tree_stats() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(5);
+		params["num_leaves"] = json!(8);
+		params["max_depth"] = json!(4);
+		let bst = _train_booster(&params);
+		let stats = bst.tree_stats().unwrap();
+		assert_eq!(stats.num_trees, bst.total_num_trees().unwrap());
+		assert!(stats.total_leaves >= stats.num_trees);
+		assert!(stats.total_leaves <= 8 * stats.num_trees);
+		assert!(stats.max_depth <= 4);
+		assert!(stats.avg_depth > 0. && stats.avg_depth <= stats.max_depth as f64);
+
+		let empty = Booster::from_file("./test/test_from_file.input").unwrap();
+		assert_eq!(
+			empty.tree_stats(),
+			Ok(TreeStats {
+				num_trees: 0,
+				total_leaves: 0,
+				avg_depth: 0.,
+				max_depth: 0,
+			})
+		);
+	}
+
+	#[test]
+	fn leaf_counts() {
+		let dataset = _read_train_file().unwrap();
+		let n_rows = dataset.n_rows().unwrap();
+		let params = json! {
+			{
+				"num_iterations": 3,
+				"objective": "binary",
+				"num_leaves": 8,
+			}
+		};
+		let bst = Booster::train(dataset, &params).unwrap();
+		let leaf_counts = bst.leaf_counts(0).unwrap();
+		assert_eq!(leaf_counts.len(), 8);
+		assert_eq!(
+			leaf_counts.iter().map(|&c| c as usize).sum::<usize>(),
+			n_rows
+		);
+		assert_eq!(
+			bst.leaf_counts(bst.total_num_trees().unwrap())
+				.unwrap_err()
+				.kind(),
+			crate::ErrorKind::InvalidInput
+		);
+	}
+
+	#[test]
+	fn train_forced_splits() {
+		let dataset = _read_train_file().unwrap();
+		let params = Params::new()
+			.objective(crate::Objective::Binary)
+			.forced_splits(&crate::ForcedSplit::new(0, 1.0))
+			.set("num_iterations", 3);
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+		assert_eq!(bst.tree_structure(0).unwrap()["split_feature"], 0);
+	}
+
+	#[test]
+	fn feature_importances() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(5);
+		let bst = _train_booster(&params);
+		let importances = bst.feature_importances(0).unwrap();
+		assert_eq!(importances.len(), 28);
+		for (i, importance) in importances.iter().enumerate() {
+			assert_eq!(importance.index, i);
+			assert_eq!(importance.name, format!("Column_{}", i));
+			assert_eq!(importance.split_count == 0, importance.total_gain == 0.);
+		}
+		assert!(importances
+			.iter()
+			.any(|importance| importance.split_count > 0));
+	}
+
+	#[test]
+	fn bin_boundaries() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(10);
+		let bst = _train_booster(&params);
+		let importance = bst.feature_importance().unwrap();
+		let most_used = (0..importance.len())
+			.max_by(|&a, &b| importance[a].total_cmp(&importance[b]))
+			.unwrap();
+		let boundaries = bst.bin_boundaries(most_used).unwrap();
+		assert!(boundaries.len() > 1);
+		assert!(boundaries.windows(2).all(|w| w[0] < w[1]));
+		assert!(bst.bin_boundaries(28).is_err());
+	}
+
+	#[test]
+	fn decision_path() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(1);
+		let bst = _train_booster(&params);
+		assert_eq!(bst.total_num_trees(), Ok(1));
+		let row = [0.5; 28];
+
+		let paths = bst.decision_path(&row).unwrap();
+		assert_eq!(paths.len(), 1);
+		let path = &paths[0];
+		assert!(!path.nodes.is_empty());
+		assert_eq!(
+			path.leaf_index,
+			bst.predict_leaf_indices(&row, 1).unwrap()[0][0]
+		);
+		let raw_score = bst
+			.predict_with_type(&row, 1, PredictType::RawScore)
+			.unwrap();
+		assert!((path.leaf_value - raw_score[0]).abs() < 1e-9);
+
+		// the path follows the tree structure from its root
+		let mut node = &bst.tree_structure(0).unwrap();
+		for path_node in &path.nodes {
+			assert_eq!(node["split_feature"], path_node.feature_index);
+			assert_eq!(node["threshold"], path_node.threshold);
+			assert_eq!(
+				row[path_node.feature_index] <= path_node.threshold,
+				path_node.went_left
+			);
+			node = &node[if path_node.went_left {
+				"left_child"
+			} else {
+				"right_child"
+			}];
+		}
+		assert_eq!(node["leaf_index"], path.leaf_index);
+		assert!(bst.decision_path(&row[1..]).is_err());
+	}
+
 	#[test]
 	fn feature_name() {
 		let params = _default_params();
@@ -655,20 +3753,531 @@ mod tests {
 		let params = _default_params();
 		let bst = _train_booster(&params);
 		let filename = "./test/test_save_string.output";
-		assert_eq!(bst.save_file(&filename), Ok(()));
+		assert_eq!(bst.save_file(filename), Ok(()));
 		assert!(Path::new(&filename).exists());
-		let booster_file_content = fs::read(&filename).unwrap();
+		let booster_file_content = fs::read(filename).unwrap();
 		let _ = fs::remove_file("./test/test_save_string.output");
 
 		assert!(!booster_file_content.is_empty());
 		assert_eq!(Ok(booster_file_content), bst.save_string())
 	}
 
+	#[test]
+	fn prediction_bounds() {
+		let n_rows = 300;
+		let data: Vec<f64> = (0..n_rows)
+			.flat_map(|i| [(i % 17) as f64, (i * 7 % 13) as f64])
+			.collect();
+		let label: Vec<f32> = (0..n_rows)
+			.map(|i| (i % 17) as f32 - (i * 7 % 13) as f32 / 2.)
+			.collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.objective(crate::Objective::Regression)
+			.min_data_in_leaf(5)
+			.set("num_iterations", 10)
+			.set("monotone_constraints", "1,-1");
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+
+		let (lower, upper) = bst.prediction_bounds().unwrap();
+		assert!(lower < upper);
+		let predictions = bst
+			.predict_with_type(&data, n_rows, PredictType::RawScore)
+			.unwrap();
+		assert!(predictions.iter().all(|p| (lower..=upper).contains(p)));
+	}
+
+	#[test]
+	fn merge() {
+		let train = |num_iterations: i32| {
+			let params = Params::new()
+				.objective(crate::Objective::Binary)
+				.set("num_iterations", num_iterations)
+				.set("data_random_seed", 0);
+			crate::TrainConfig::new(params)
+				.run(_read_train_file().unwrap())
+				.unwrap()
+		};
+		let mut bst = train(10);
+		let other = train(5);
+		assert_eq!(bst.total_num_trees(), Ok(10));
+		assert_eq!(other.total_num_trees(), Ok(5));
+
+		let data = (0..5 * 28).map(|i| (i % 7) as f64 / 7.).collect::<Vec<_>>();
+		let raw = bst
+			.predict_with_type(&data, 5, PredictType::RawScore)
+			.unwrap();
+		let other_raw = other
+			.predict_with_type(&data, 5, PredictType::RawScore)
+			.unwrap();
+		assert_eq!(bst.merge(&other), Ok(()));
+		assert_eq!(bst.total_num_trees(), Ok(15));
+		assert_eq!(other.total_num_trees(), Ok(5));
+		let merged_raw = bst
+			.predict_with_type(&data, 5, PredictType::RawScore)
+			.unwrap();
+		for ((merged, raw), other_raw) in merged_raw.iter().zip(&raw).zip(&other_raw) {
+			assert!((merged - (raw + other_raw)).abs() < 1e-9);
+		}
+
+		let small_data = [0., 1., 1., 0., 0., 0., 1., 1.];
+		let small_dataset = Dataset::from_mat(&small_data, 4, &[0., 1., 0., 1.]).unwrap();
+		let small = Booster::train_with_params(small_dataset, &Params::new()).unwrap();
+		assert!(bst.merge(&small).is_err());
+		assert_eq!(bst.total_num_trees(), Ok(15));
+	}
+
+	#[test]
+	fn save_model_rounded() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(20);
+		let bst = _train_booster(&params);
+		let full_filename = "./test/test_save_model_rounded_full.output";
+		let filename = "./test/test_save_model_rounded.output";
+		bst.save_file(full_filename).unwrap();
+		assert_eq!(bst.save_model_rounded(filename, 3), Ok(()));
+		let full_len = fs::metadata(full_filename).unwrap().len();
+		let rounded_len = fs::metadata(filename).unwrap().len();
+		let rounded_bst = Booster::from_file(filename).unwrap();
+		let _ = fs::remove_file(full_filename);
+		let _ = fs::remove_file(filename);
+		assert!(rounded_len < full_len);
+
+		let data = (0..10 * 28)
+			.map(|i| (i % 11) as f64 / 11.)
+			.collect::<Vec<_>>();
+		let num_trees = bst.current_iteration().unwrap() as f64;
+		let expected = bst
+			.predict_with_type(&data, 10, PredictType::RawScore)
+			.unwrap();
+		let rounded = rounded_bst
+			.predict_with_type(&data, 10, PredictType::RawScore)
+			.unwrap();
+		for (expected, rounded) in expected.iter().zip(&rounded) {
+			assert!((expected - rounded).abs() <= 0.0005 * num_trees);
+		}
+	}
+
+	#[test]
+	fn round_literal() {
+		assert_eq!(super::round_literal("0.123456", 3), "0.123");
+		assert_eq!(super::round_literal("-1.5e-05", 3), "-0");
+		assert_eq!(super::round_literal("2.5000001", 2), "2.5");
+		assert_eq!(super::round_literal("12", 1), "12");
+		assert_eq!(super::round_literal("0.123", 0), "0");
+	}
+
+	#[test]
+	fn continue_from_file() {
+		let n_rows = 200;
+		let data: Vec<f64> = (0..n_rows)
+			.flat_map(|i| [(i % 17) as f64, (i * 7 % 13) as f64])
+			.collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 17 > 8) as u8 as f32).collect();
+		let params = Params::new()
+			.objective(crate::Objective::Binary)
+			.min_data_in_leaf(5)
+			.set("num_iterations", 6);
+
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+		let num_trees = bst.total_num_trees().unwrap();
+		assert!(num_trees > 0);
+		let filename = "./test/test_continue_from_file.output";
+		bst.save_file(filename).unwrap();
+
+		let mut dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let no_init_score = Booster::continue_from_file(
+			filename,
+			Dataset::from_mat(&data, n_rows, &label).unwrap(),
+			&params,
+			4,
+		);
+		let raw_scores = bst
+			.predict_with_type(&data, n_rows, PredictType::RawScore)
+			.unwrap();
+		dataset.set_init_score(&raw_scores).unwrap();
+		let continued = Booster::continue_from_file(filename, dataset, &params, 4);
+		let _ = fs::remove_file(filename);
+
+		assert!(no_init_score.is_err());
+		assert_eq!(continued.unwrap().total_num_trees().unwrap(), num_trees + 4);
+	}
+
+	#[test]
+	fn continue_from_file_feature_mismatch() {
+		let data = [1.0, 0.1, 0.2, 0.7, 0.4, 0.5];
+		let mut dataset = Dataset::from_mat(&data, 2, &[0.0, 1.0]).unwrap();
+		dataset.set_init_score(&[0.0, 0.0]).unwrap();
+		let error = Booster::continue_from_file(
+			"./test/test_from_file.input",
+			dataset,
+			&Params::new().objective(crate::Objective::Binary),
+			1,
+		)
+		.err()
+		.unwrap();
+		assert_eq!(error.kind(), crate::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn model_size_bytes() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(3);
+		let small = _train_booster(&params).model_size_bytes().unwrap();
+		params["num_iterations"] = json!(10);
+		let large = _train_booster(&params).model_size_bytes().unwrap();
+		assert!(small > 0);
+		assert!(large > small);
+	}
+
+	#[test]
+	fn num_classes_and_objective() {
+		let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		assert_eq!(bst.num_classes(), Ok(1));
+
+		let n_rows = 30;
+		let data: Vec<f64> = (0..n_rows).map(|i| (i % 3) as f64).collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 3) as f32).collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.min_data_in_leaf(1)
+			.objective(crate::Objective::MulticlassSoftmax { num_class: 3 });
+		let filename = "./test/test_num_classes.output";
+		Booster::train_with_params(dataset, &params)
+			.unwrap()
+			.save_file(filename)
+			.unwrap();
+		let bst = Booster::from_file(filename);
+		let _ = fs::remove_file(filename);
+		let bst = bst.unwrap();
+		assert_eq!(bst.num_classes(), Ok(3));
+		assert!(bst.objective().unwrap().contains("multiclass"));
+	}
+
+	#[test]
+	fn task() {
+		let binary_bst = _train_booster(&_default_params());
+		assert_eq!(binary_bst.task(), Ok(Task::Classification { num_class: 1 }));
+
+		let n_rows = 30;
+		let data: Vec<f64> = (0..n_rows).map(|i| (i % 3) as f64).collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 3) as f32).collect();
+		let train = |objective| {
+			let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+			let params = Params::new()
+				.set("num_iterations", 3)
+				.min_data_in_leaf(1)
+				.objective(objective);
+			Booster::train_with_params(dataset, &params).unwrap()
+		};
+		assert_eq!(
+			train(crate::Objective::Regression).task(),
+			Ok(Task::Regression)
+		);
+		assert_eq!(
+			train(crate::Objective::MulticlassSoftmax { num_class: 3 }).task(),
+			Ok(Task::Classification { num_class: 3 })
+		);
+	}
+
+	#[test]
+	fn predict_colmajor() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(5);
+		let mut bst = _train_booster(&params);
+		let n_rows = 4;
+		let rows = (0..n_rows * 28)
+			.map(|i| (i % 11) as f64 / 11.)
+			.collect::<Vec<_>>();
+		let columns: Vec<Vec<f64>> = (0..28)
+			.map(|j| (0..n_rows).map(|i| rows[i * 28 + j]).collect())
+			.collect();
+		let columns: Vec<&[f64]> = columns.iter().map(Vec::as_slice).collect();
+		assert_eq!(
+			bst.predict_colmajor(&columns, PredictType::Normal),
+			bst.predict(&rows)
+		);
+		assert_eq!(
+			bst.predict_colmajor(&columns, PredictType::Contrib),
+			bst.predict_with_type(&rows, n_rows, PredictType::Contrib)
+		);
+		assert!(bst
+			.predict_colmajor(&columns[1..], PredictType::Normal)
+			.is_err());
+		let mut ragged = columns.clone();
+		ragged[3] = &ragged[3][1..];
+		assert!(bst.predict_colmajor(&ragged, PredictType::Normal).is_err());
+	}
+
+	#[test]
+	fn predict_into() {
+		let bst = _train_booster(&_default_params());
+		let data = [0.5; 3 * 28];
+		let expected = bst
+			.predict_with_type(&data, 3, PredictType::Normal)
+			.unwrap();
+		let mut out = [0.; 3];
+		assert_eq!(
+			bst.predict_into(&data, 3, PredictType::Normal, &mut out),
+			Ok(())
+		);
+		assert_eq!(out.to_vec(), expected);
+		let mut too_short = [0.; 2];
+		assert!(bst
+			.predict_into(&data, 3, PredictType::Normal, &mut too_short)
+			.is_err());
+		assert_eq!(
+			bst.predict_into(&[], 0, PredictType::Normal, &mut []),
+			Ok(())
+		);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn predict_mmap() {
+		use std::{io::Write, os::unix::io::AsRawFd};
+
+		let bst = _train_booster(&_default_params());
+		let n_rows = 4;
+		let data = (0..n_rows * 28)
+			.map(|i| (i % 9) as f64 / 9.)
+			.collect::<Vec<_>>();
+		let filename = "./test/test_predict_mmap.output";
+		let mut file = fs::File::create(filename).unwrap();
+		for value in &data {
+			file.write_all(&value.to_ne_bytes()).unwrap();
+		}
+		drop(file);
+
+		let file = fs::File::open(filename).unwrap();
+		let len = data.len() * std::mem::size_of::<f64>();
+		// SAFETY: the file is at least `len` bytes, and the mapping is page-aligned
+		let mmap = unsafe {
+			let ptr = libc::mmap(
+				std::ptr::null_mut(),
+				len,
+				libc::PROT_READ,
+				libc::MAP_PRIVATE,
+				file.as_raw_fd(),
+				0,
+			);
+			assert_ne!(ptr, libc::MAP_FAILED);
+			std::slice::from_raw_parts(ptr as *const f64, data.len())
+		};
+		let mut out = vec![0.; n_rows];
+		let result = bst.predict_mmap(mmap, n_rows, PredictType::Normal, &mut out);
+		// SAFETY: `mmap` isn't used anymore
+		unsafe { libc::munmap(mmap.as_ptr() as *mut c_void, len) };
+		let _ = fs::remove_file(filename);
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			Ok(out),
+			bst.predict_with_type(&data, n_rows, PredictType::Normal)
+		);
+	}
+
+	#[test]
+	fn predict_classes() {
+		// each row's class is the value of its only feature
+		let n_rows = 30;
+		let data: Vec<f64> = (0..n_rows).map(|i| (i % 3) as f64).collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 3) as f32).collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 5)
+			.min_data_in_leaf(1)
+			.objective(crate::Objective::MulticlassSoftmax { num_class: 3 });
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+
+		let rows = [2., 0., 1.];
+		let flat = bst
+			.predict_with_type(&rows, 3, PredictType::Normal)
+			.unwrap();
+		let classes = bst.predict_classes(&rows, 3).unwrap();
+		assert_eq!(classes.concat(), flat);
+		assert_eq!(classes.len(), 3);
+		for (row, &class) in classes.iter().zip(&rows) {
+			assert_eq!(row.len(), 3);
+			assert!((row.iter().sum::<f64>() - 1.).abs() < 1e-9);
+			// the highest probability is at the row's class, which a class-major layout
+			// would have broken
+			let top = (0..3).max_by(|&a, &b| row[a].total_cmp(&row[b])).unwrap();
+			assert_eq!(top, class as usize);
+		}
+
+		let binary_bst = _train_booster(&_default_params());
+		let binary_classes = binary_bst.predict_classes(&[0.5; 2 * 28], 2).unwrap();
+		assert_eq!(binary_classes.len(), 2);
+		assert!(binary_classes.iter().all(|row| row.len() == 1));
+	}
+
+	#[test]
+	fn predict_top_class() {
+		let n_rows = 30;
+		let data: Vec<f64> = (0..n_rows).map(|i| (i % 3) as f64).collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 3) as f32).collect();
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 5)
+			.min_data_in_leaf(1)
+			.objective(crate::Objective::MulticlassSoftmax { num_class: 3 });
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+		let rows = [0., 1., 2., 1.];
+		let top_classes = bst.predict_top_class(&rows, 4).unwrap();
+		let probabilities = bst
+			.predict_with_type(&rows, 4, PredictType::Normal)
+			.unwrap();
+		assert_eq!(top_classes.len(), 4);
+		for (top_class, probabilities) in top_classes.iter().zip(probabilities.chunks(3)) {
+			assert!(probabilities.iter().all(|&p| p <= top_class.probability));
+			assert_eq!(probabilities[top_class.class], top_class.probability);
+		}
+		assert_eq!(
+			top_classes.iter().map(|c| c.class).collect::<Vec<_>>(),
+			vec![0, 1, 2, 1]
+		);
+
+		let binary_bst = _train_booster(&_default_params());
+		let p = binary_bst
+			.predict_with_type(&[0.5; 28], 1, PredictType::Normal)
+			.unwrap()[0];
+		let top_class = binary_bst.predict_top_class(&[0.5; 28], 1).unwrap()[0];
+		assert_eq!(top_class.class, (p >= 0.5) as usize);
+		assert!((top_class.probability - p.max(1. - p)).abs() < 1e-12);
+	}
+
+	#[test]
+	fn blend_predictions() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(3);
+		let bst = _train_booster(&params);
+		let same_bst = _train_booster(&params);
+		let data = [0.5; 2 * 28];
+		let prediction = bst
+			.predict_with_type(&data, 2, PredictType::Normal)
+			.unwrap();
+		let blended = super::blend_predictions(&[&bst, &same_bst], &data, 2, None).unwrap();
+		let weighted =
+			super::blend_predictions(&[&bst, &same_bst], &data, 2, Some(&[1., 3.])).unwrap();
+		for ((p, blended), weighted) in prediction.iter().zip(&blended).zip(&weighted) {
+			assert!((p - blended).abs() < 1e-12);
+			assert!((p - weighted).abs() < 1e-12);
+		}
+
+		assert!(super::blend_predictions(&[], &data, 2, None).is_err());
+		assert!(super::blend_predictions(&[&bst, &same_bst], &data, 2, Some(&[1.])).is_err());
+		assert!(super::blend_predictions(&[&bst], &data, 2, Some(&[0.])).is_err());
+	}
+
+	#[test]
+	fn feature_types() {
+		let n_rows = 200;
+		let data = (0..n_rows)
+			.flat_map(|row| [(row % 4) as f64, row as f64 / 10.])
+			.collect::<Vec<_>>();
+		let label = (0..n_rows)
+			.map(|row| (row % 4 < 2) as u8 as f32)
+			.collect::<Vec<_>>();
+		let dataset = Dataset::from_mat_with_params(
+			&data,
+			n_rows,
+			&label,
+			&Params::new().set("categorical_feature", "0"),
+		)
+		.unwrap();
+		let params = Params::new()
+			.objective(crate::Objective::Binary)
+			.set("num_iterations", 3);
+		let bst = Booster::train_with_params(dataset, &params).unwrap();
+		assert_eq!(
+			bst.feature_types().unwrap(),
+			vec![FeatureType::Categorical, FeatureType::Numerical]
+		);
+
+		let bst = _train_booster(&_default_params());
+		assert_eq!(
+			bst.feature_types().unwrap(),
+			vec![FeatureType::Numerical; 28]
+		);
+	}
+
+	#[test]
+	fn training_params() {
+		let mut params = _default_params();
+		params["num_leaves"] = json!(13);
+		let bst = _train_booster(&params);
+		let training_params = bst.training_params().unwrap();
+		assert_eq!(
+			training_params.get("num_leaves").map(String::as_str),
+			Some("13")
+		);
+		assert_eq!(
+			training_params.get("objective").map(String::as_str),
+			Some("binary")
+		);
+
+		let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+		assert_eq!(
+			bst.training_params()
+				.unwrap()
+				.get("boosting")
+				.map(String::as_str),
+			Some("gbdt")
+		);
+	}
+
+	#[test]
+	fn train_num_iterations() {
+		let params = Params::new()
+			.objective(crate::Objective::Binary)
+			.set("num_iterations", 3);
+		let bst = Booster::train_with_params(_read_train_file().unwrap(), &params).unwrap();
+		assert_eq!(bst.total_num_trees(), Ok(3));
+		let run = crate::TrainConfig::new(params)
+			.run(_read_train_file().unwrap())
+			.unwrap();
+		assert_eq!(run.total_num_trees(), Ok(3));
+
+		// aliases are resolved to num_iterations
+		let params = Params::new()
+			.objective(crate::Objective::Binary)
+			.set("num_trees", 3);
+		let bst = Booster::train_with_params(_read_train_file().unwrap(), &params).unwrap();
+		assert_eq!(bst.total_num_trees(), Ok(3));
+	}
+
+	#[test]
+	fn truncate() {
+		let mut params = _default_params();
+		params["num_iterations"] = json!(50);
+		let mut bst = _train_booster(&params);
+		assert_eq!(bst.total_num_trees(), Ok(50));
+		let size = bst.save_string().unwrap().len();
+
+		bst.truncate(10).unwrap();
+		assert_eq!(bst.total_num_trees(), Ok(10));
+		assert_eq!(bst.current_iteration(), Ok(10));
+		assert!(bst.save_string().unwrap().len() < size);
+		assert!(bst.truncate(11).is_err());
+		assert!(bst.truncate(0).is_err());
+	}
+
 	#[test]
 	fn from_file() {
 		let _ = Booster::from_file("./test/test_from_file.input").unwrap();
 	}
 
+	#[test]
+	fn from_file_with_nul() {
+		assert!(Booster::from_file("./test/test_from\0file.input").is_err());
+		assert!(
+			Booster::from_file_with_param_overrides("./test/test_from_file.input", "a\0").is_err()
+		);
+	}
+
 	#[test]
 	fn from_string() {
 		let file = fs::read_to_string("./test/test_from_file.input").unwrap();
@@ -0,0 +1,125 @@
+//! Cross-validation driven from Rust, as the LightGBM C API doesn't provide it.
+
+use crate::{Booster, Dataset, Error, Params, Result};
+
+/// Find a good number of boosting iterations for `params` on `data` with `n_folds`-fold
+/// cross-validation, returning the mean best iteration across folds.
+///
+/// For each fold, a model is trained on the other folds for up to `max_iterations`
+/// iterations, and stops when the first metric on the fold hasn't improved for
+/// `early_stopping_rounds` iterations. The metric is the first one set in `params` (e.g.
+/// `metric=auc`), or the default metric of the objective. Rows are assigned to folds in
+/// turn (row `i` to fold `i % n_folds`), so the result is deterministic.
+///
+/// `num_iterations` in `params` is ignored.
+///
+/// Example
+/// ```
+/// use lightgbm::{Dataset, Objective, Params};
+///
+/// let dataset =
+/// 	Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+/// 		.unwrap();
+/// let params = Params::new().objective(Objective::Binary).set("metric", "auc");
+/// let num_iterations = lightgbm::suggest_num_iterations(&dataset, &params, 50, 3, 5).unwrap();
+/// let params = params.set("num_iterations", num_iterations);
+/// ```
+pub fn suggest_num_iterations(
+	data: &Dataset,
+	params: &Params,
+	max_iterations: i32,
+	n_folds: usize,
+	early_stopping_rounds: usize,
+) -> Result<i32> {
+	if max_iterations < 1 {
+		return Err(Error::invalid_input(format!(
+			"max_iterations should be at least 1, got {max_iterations}"
+		)));
+	}
+	let n_rows = data.n_rows()?;
+	if n_folds < 2 || n_folds > n_rows {
+		return Err(Error::invalid_input(format!(
+			"n_folds should be in 2..={n_rows} (the number of rows), got {n_folds}"
+		)));
+	}
+	let n_rows: i32 = n_rows
+		.try_into()
+		.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+
+	let mut total_best_iteration = 0_i64;
+	for fold in 0..n_folds {
+		let (valid_indices, train_indices): (Vec<i32>, Vec<i32>) =
+			(0..n_rows).partition(|&row| row as usize % n_folds == fold);
+		let mut booster = Booster::create(data.subset(&train_indices)?, params)?;
+		booster.add_valid_data(data.subset(&valid_indices)?)?;
+
+		let mut best: Option<(f64, i32)> = None;
+		for iteration in 1..=max_iterations {
+			if booster.update_one_iter()? {
+				break;
+			}
+			let (metric, score) = booster
+				.eval(1)?
+				.into_iter()
+				.next()
+				.ok_or_else(|| Error::invalid_input("cross-validation requires a metric"))?;
+			let improved = match best {
+				None => true,
+				Some((best_score, _)) if is_higher_better(&metric) => score > best_score,
+				Some((best_score, _)) => score < best_score,
+			};
+			if improved {
+				best = Some((score, iteration));
+			} else if let Some((_, best_iteration)) = best {
+				if (iteration - best_iteration) as usize >= early_stopping_rounds {
+					break;
+				}
+			}
+		}
+		total_best_iteration += i64::from(best.map_or(1, |(_, best_iteration)| best_iteration));
+	}
+	let mean = (total_best_iteration as f64 / n_folds as f64).round() as i32;
+	Ok(mean.max(1))
+}
+
+/// Whether a higher value of the metric named `metric` (as in [`Booster::eval_names`]) means
+/// a better model.
+fn is_higher_better(metric: &str) -> bool {
+	// ranking metrics are named after their cutoff, e.g. `ndcg@5`
+	let name = metric.split('@').next().unwrap_or(metric);
+	matches!(
+		name,
+		"auc" | "auc_mu" | "ndcg" | "map" | "average_precision"
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, crate::Objective};
+
+	#[test]
+	fn suggest_num_iterations() {
+		let dataset =
+			Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+				.unwrap();
+		let params = Params::new()
+			.objective(Objective::Binary)
+			.set("metric", "auc")
+			.set("data_random_seed", 0);
+		let num_iterations = super::suggest_num_iterations(&dataset, &params, 30, 3, 3).unwrap();
+		assert!((1..=30).contains(&num_iterations));
+
+		assert!(super::suggest_num_iterations(&dataset, &params, 30, 1, 3).is_err());
+		assert!(super::suggest_num_iterations(&dataset, &params, 0, 3, 3).is_err());
+	}
+
+	#[test]
+	fn higher_is_better() {
+		assert!(is_higher_better("auc"));
+		assert!(is_higher_better("ndcg@5"));
+		assert!(!is_higher_better("binary_logloss"));
+		assert!(!is_higher_better("l2"));
+		assert!(is_higher_better("map@3"));
+		assert!(!is_higher_better("mape"));
+	}
+}
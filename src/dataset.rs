@@ -1,13 +1,21 @@
 use {
 	libc::{c_char, c_void},
 	lightgbm_sys,
-	std::{self, ffi::CString},
+	std::{
+		self,
+		collections::HashSet,
+		ffi::{CStr, CString},
+		fs::{self, File},
+		io::{BufRead, BufReader, BufWriter, Read, Write},
+		path::{Path, PathBuf},
+		sync::atomic::{AtomicUsize, Ordering},
+	},
 };
 
 #[cfg(feature = "dataframe")]
 use polars::prelude::*;
 
-use crate::{Error, Result};
+use crate::{Error, Params, Result};
 
 /// Dataset used throughout LightGBM for training.
 ///
@@ -43,6 +51,11 @@ use crate::{Error, Result};
 /// 	Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
 /// 		.unwrap();
 /// ```
+///
+/// LightGBM copies the data it's given into its own representation, including the raw
+/// feature values it keeps for [linear trees](Params::linear_tree), so the input buffers can
+/// be dropped as soon as the dataset is constructed. The Python package's `free_raw_data`
+/// option has no equivalent here, as there is no borrowed data to keep alive.
 pub struct Dataset {
 	pub(crate) handle: lightgbm_sys::DatasetHandle,
 }
@@ -59,6 +72,28 @@ impl Dataset {
 		Self { handle }
 	}
 
+	/// Wrap a dataset handle created through the LightGBM C API, e.g. by C code, taking
+	/// ownership of it: the dataset is freed when the returned `Dataset` is dropped.
+	///
+	/// # Safety
+	///
+	/// `handle` must be a valid dataset handle, as created by the `LGBM_DatasetCreate*`
+	/// functions of the LightGBM library this crate links to, and must not be used or freed
+	/// elsewhere afterwards, otherwise it would be freed twice.
+	pub unsafe fn from_raw_handle(handle: lightgbm_sys::DatasetHandle) -> Self {
+		Self::new(handle)
+	}
+
+	/// Release ownership of the underlying dataset handle, e.g. to pass it to C code.
+	///
+	/// The dataset isn't freed: this is up to the caller, either with `LGBM_DatasetFree` or by
+	/// wrapping the handle again with [`from_raw_handle`](Self::from_raw_handle).
+	pub fn into_raw_handle(self) -> lightgbm_sys::DatasetHandle {
+		let handle = self.handle;
+		std::mem::forget(self);
+		handle
+	}
+
 	/// Create a new `Dataset` from dense array in row-major order.
 	///
 	/// Example
@@ -81,35 +116,199 @@ impl Dataset {
 	/// .unwrap();
 	/// ```
 	pub fn from_mat(data: &[f64], n_rows: usize, label: &[f32]) -> Result<Self> {
-		let data_length = data.len();
-		if (data_length != 0 || n_rows != 0) && data_length % n_rows != 0 {
-			return Err(Error::new(format!(
-				"data len is not multiple of n_rows ({n_rows}), but all rows \
-					should have the same number of features",
+		Self::from_mat_with_params(data, n_rows, label, &Params::new())
+	}
+
+	/// Create a new `Dataset` from dense array in row-major order, with an explicit number of
+	/// features.
+	///
+	/// [`from_mat`](Self::from_mat) infers the number of features from the length of `data`,
+	/// so malformed data whose length happens to be a multiple of `n_rows`, e.g. a transposed
+	/// matrix, goes unnoticed. This errors unless `data` holds exactly
+	/// `n_rows * n_features` values.
+	pub fn from_mat_shaped(
+		data: &[f64],
+		n_rows: usize,
+		n_features: usize,
+		label: &[f32],
+	) -> Result<Self> {
+		if n_rows.checked_mul(n_features) != Some(data.len()) {
+			return Err(Error::invalid_input(format!(
+				"data len ({}) is not n_rows ({n_rows}) * n_features ({n_features})",
+				data.len(),
 			)));
 		}
-		let feature_length = if data_length == 0 && n_rows == 0 {
-			0
-		} else {
-			data_length / n_rows
-		};
+		let nrow = n_rows
+			.try_into()
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		let ncol = n_features
+			.try_into()
+			.map_err(|_| Error::conversion("number of columns doesn't fit into an i32"))?;
+		Self::from_mat_inner(data, (nrow, ncol), true, label, &Params::new(), None)
+	}
+
+	/// Create a new `Dataset` from dense array in row-major order, with `f64` labels.
+	///
+	/// LightGBM stores labels as `f32`, so they are narrowed, losing precision beyond about 7
+	/// significant digits. See [`from_mat`](Self::from_mat).
+	pub fn from_mat_f64_label(data: &[f64], n_rows: usize, label: &[f64]) -> Result<Self> {
+		let label = label.iter().map(|&label| label as f32).collect::<Vec<_>>();
+		Self::from_mat(data, n_rows, &label)
+	}
+
+	/// Create a new `Dataset` from an owned dense array in row-major order.
+	///
+	/// This is [`from_mat`](Self::from_mat) for when the data has already been collected into
+	/// vectors: LightGBM copies the data into its own binned representation, so the vectors
+	/// are freed as soon as the dataset is constructed.
+	pub fn from_vec(data: Vec<f64>, n_rows: usize, label: Vec<f32>) -> Result<Self> {
+		Self::from_mat(&data, n_rows, &label)
+	}
+
+	/// Create a new `Dataset` from rows of any numeric type that converts to `f64`, e.g. `f32`,
+	/// `i32` or `u8`.
+	///
+	/// All rows must have the same length, which is the number of features. Values are
+	/// converted to `f64` while being laid out in row-major order, so this takes a single
+	/// copy of the data, as [`from_mat`](Self::from_mat) would need anyway.
+	///
+	/// Example
+	/// ```
+	/// use lightgbm::Dataset;
+	///
+	/// let rows: Vec<Vec<f32>> = vec![vec![1.0, 0.1], vec![0.7, 0.4], vec![0.9, 0.8]];
+	/// let dataset = Dataset::from_rows_generic(&rows, &[0.0, 0.0, 1.0]).unwrap();
+	/// ```
+	pub fn from_rows_generic<T: Into<f64> + Copy>(rows: &[Vec<T>], label: &[f32]) -> Result<Self> {
+		let n_features = rows.first().map_or(0, Vec::len);
+		let mut data = Vec::with_capacity(rows.len() * n_features);
+		for (i, row) in rows.iter().enumerate() {
+			if row.len() != n_features {
+				return Err(Error::invalid_input(format!(
+					"row {i} has {} values, but row 0 has {n_features}",
+					row.len()
+				)));
+			}
+			data.extend(row.iter().map(|&value| value.into()));
+		}
+		Self::from_mat(&data, rows.len(), label)
+	}
+
+	/// Create a new `Dataset` from dense array in row-major order, with feature names.
+	///
+	/// There must be exactly one name per feature. Models trained on this dataset record
+	/// these names instead of `Column_0`, `Column_1`...
+	///
+	/// Example
+	/// ```
+	/// use lightgbm::Dataset;
+	///
+	/// let data = &[[1.0, 0.1], [0.7, 0.4], [0.9, 0.8]];
+	/// let label = &[0.0, 0.0, 1.0];
+	/// let dataset = Dataset::from_mat_named(
+	/// 	&data.iter().flatten().copied().collect::<Vec<_>>(),
+	/// 	data.len(),
+	/// 	label,
+	/// 	&["age", "income"],
+	/// )
+	/// .unwrap();
+	/// ```
+	pub fn from_mat_named(
+		data: &[f64],
+		n_rows: usize,
+		label: &[f32],
+		feature_names: &[&str],
+	) -> Result<Self> {
+		let mut dataset = Self::from_mat(data, n_rows, label)?;
+		dataset.set_feature_names(feature_names)?;
+		Ok(dataset)
+	}
 
+	/// Create a new `Dataset` from dense array in row-major order, with dataset parameters.
+	///
+	/// Some parameters, such as [`Params::linear_tree`], have to be set when the dataset is
+	/// constructed rather than only when training. Parameters are [validated](Params::validate)
+	/// first.
+	pub fn from_mat_with_params(
+		data: &[f64],
+		n_rows: usize,
+		label: &[f32],
+		params: &Params,
+	) -> Result<Self> {
+		let shape = mat_shape(data.len(), n_rows)?;
+		Self::from_mat_inner(data, shape, true, label, params, None)
+	}
+
+	/// Create a new `Dataset` from dense array in row-major order, using the binning of
+	/// `reference`.
+	///
+	/// This is how validation datasets should be constructed, as LightGBM requires them to be
+	/// aligned with the training dataset.
+	pub fn from_mat_with_reference(
+		data: &[f64],
+		n_rows: usize,
+		label: &[f32],
+		reference: &Dataset,
+	) -> Result<Self> {
+		let shape = mat_shape(data.len(), n_rows)?;
+		Self::from_mat_inner(data, shape, true, label, &Params::new(), Some(reference))
+	}
+
+	/// Create a new `Dataset` from dense array in column-major order, i.e. all the values of
+	/// the first feature, then all the values of the second feature...
+	///
+	/// This avoids transposing data that is stored by column. `data` must hold
+	/// `n_rows * n_features` values.
+	///
+	/// Example
+	/// ```
+	/// use lightgbm::Dataset;
+	///
+	/// let feature_1 = [1.0, 0.7, 0.9];
+	/// let feature_2 = [0.1, 0.4, 0.8];
+	/// let label = &[0.0, 0.0, 1.0];
+	/// let dataset = Dataset::from_mat_colmajor(&[feature_1, feature_2].concat(), 3, 2, label)
+	/// 	.unwrap();
+	/// ```
+	pub fn from_mat_colmajor(
+		data: &[f64],
+		n_rows: usize,
+		n_features: usize,
+		label: &[f32],
+	) -> Result<Self> {
+		if n_rows.checked_mul(n_features) != Some(data.len()) {
+			return Err(Error::invalid_input(format!(
+				"data len ({}) is not n_rows ({n_rows}) * n_features ({n_features})",
+				data.len(),
+			)));
+		}
 		let nrow = n_rows
 			.try_into()
-			.map_err(|_| Error::new("number of rows doesn't fit into an i32"))?;
-		let ncol = feature_length
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		let ncol = n_features
 			.try_into()
-			.map_err(|_| Error::new("number of columns doesn't fit into an i32"))?;
+			.map_err(|_| Error::conversion("number of columns doesn't fit into an i32"))?;
+		Self::from_mat_inner(data, (nrow, ncol), false, label, &Params::new(), None)
+	}
+
+	fn from_mat_inner(
+		data: &[f64],
+		(nrow, ncol): (i32, i32),
+		is_row_major: bool,
+		label: &[f32],
+		params: &Params,
+		reference: Option<&Dataset>,
+	) -> Result<Self> {
 		let label_len = label
 			.len()
 			.try_into()
-			.map_err(|_| Error::new("label length doesn't fit into an i32"))?;
+			.map_err(|_| Error::conversion("label length doesn't fit into an i32"))?;
 
-		let params =
-			CString::new("").map_err(|e| Error::from_other("failed to make cstring", e))?;
+		params.validate()?;
+		let params = params.to_cstring()?;
 		let label_str =
 			CString::new("label").map_err(|e| Error::from_other("failed to make cstring", e))?;
-		let reference = std::ptr::null_mut(); // not use
+		let reference = reference.map_or(std::ptr::null_mut(), |reference| reference.handle);
 		let mut handle = std::ptr::null_mut();
 
 		lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromMat(
@@ -117,7 +316,7 @@ impl Dataset {
 			lightgbm_sys::C_API_DTYPE_FLOAT64,
 			nrow,
 			ncol,
-			1_i32,
+			i32::from(is_row_major),
 			params.as_ptr() as *const c_char,
 			reference,
 			&mut handle
@@ -137,6 +336,102 @@ impl Dataset {
 		Ok(dataset)
 	}
 
+	/// Create an empty `Dataset` of `total_n_rows` records, binned from a sample of the data,
+	/// to be filled with [`push_rows`](Self::push_rows).
+	///
+	/// This allows binning data that doesn't fit in memory at once:
+	/// 1. Sample `n_sample_rows` of the records (e.g. uniformly at random).
+	/// 2. For each feature `j`, put the values of the sampled records that aren't zero in
+	///    `sample_data[j]`, and their index among the sampled records (in `0..n_sample_rows`,
+	///    increasing) in `sample_indices[j]`. Zeros may be omitted, as values that aren't
+	///    listed are zeros.
+	/// 3. Call this, which computes the bins of the features from the sample.
+	/// 4. Push all `total_n_rows` records, in as many chunks as needed, with
+	///    [`push_rows`](Self::push_rows). The dataset is ready once the last record is
+	///    pushed.
+	/// 5. Set the labels (and other fields) with [`set_label`](Self::set_label).
+	///
+	/// `params` are the dataset parameters, e.g. `max_bin`.
+	pub fn from_sampled_columns(
+		sample_data: &[&[f64]],
+		sample_indices: &[&[i32]],
+		n_sample_rows: usize,
+		total_n_rows: usize,
+		params: &Params,
+	) -> Result<Self> {
+		if sample_data.len() != sample_indices.len() {
+			return Err(Error::invalid_input(format!(
+				"got sample data for {} features, but sample indices for {}",
+				sample_data.len(),
+				sample_indices.len()
+			)));
+		}
+		let n_sample_rows_i32: i32 = n_sample_rows
+			.try_into()
+			.map_err(|_| Error::conversion("number of sample rows doesn't fit into an i32"))?;
+		let num_per_col = sample_data
+			.iter()
+			.zip(sample_indices)
+			.enumerate()
+			.map(|(j, (values, indices))| {
+				if values.len() != indices.len() {
+					return Err(Error::invalid_input(format!(
+						"got {} sample values but {} sample indices for feature {j}",
+						values.len(),
+						indices.len()
+					)));
+				}
+				let is_increasing = indices.windows(2).all(|w| w[0] < w[1]);
+				let is_in_sample = indices
+					.iter()
+					.all(|&idx| idx >= 0 && idx < n_sample_rows_i32);
+				if !is_increasing || !is_in_sample {
+					return Err(Error::invalid_input(format!(
+						"sample indices of feature {j} should be increasing and in 0..{n_sample_rows}"
+					)));
+				}
+				values.len().try_into().map_err(|_| {
+					Error::conversion("number of sample values doesn't fit into an i32")
+				})
+			})
+			.collect::<Result<Vec<i32>>>()?;
+		let ncol = num_per_col
+			.len()
+			.try_into()
+			.map_err(|_| Error::conversion("number of columns doesn't fit into an i32"))?;
+		let num_local_row: i32 = total_n_rows
+			.try_into()
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+
+		// LightGBM doesn't write to the sample, it only takes mutable pointers
+		let mut sample_data_ptrs = sample_data
+			.iter()
+			.map(|values| values.as_ptr() as *mut f64)
+			.collect::<Vec<_>>();
+		let mut sample_indices_ptrs = sample_indices
+			.iter()
+			.map(|indices| indices.as_ptr() as *mut i32)
+			.collect::<Vec<_>>();
+		params.validate()?;
+		let params = params.to_cstring()?;
+		let mut handle = std::ptr::null_mut();
+
+		lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromSampledColumn(
+			sample_data_ptrs.as_mut_ptr(),
+			sample_indices_ptrs.as_mut_ptr(),
+			ncol,
+			num_per_col.as_ptr(),
+			n_sample_rows_i32,
+			num_local_row,
+			num_local_row.into(),
+			params.as_ptr() as *const c_char,
+			&mut handle
+		))?;
+		// It is very important to create the dataset immediately after a successful call to avoid
+		// memory leak on subsequent error (as we rely on the drop impl of Dataset to be called)
+		Ok(Self::new(handle))
+	}
+
 	/// Create a new `Dataset` from file.
 	///
 	/// file is `tsv`.
@@ -157,11 +452,50 @@ impl Dataset {
 	/// let dataset =
 	/// 	Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train");
 	/// ```
-	pub fn from_file(file_path: &str) -> Result<Self> {
-		let file_path_str =
-			CString::new(file_path).map_err(|e| Error::from_other("failed to make cstring", e))?;
-		let params =
-			CString::new("").map_err(|e| Error::from_other("failed to make cstring", e))?;
+	pub fn from_file(file_path: impl AsRef<Path>) -> Result<Self> {
+		Self::from_file_with_params(file_path, &Params::new())
+	}
+
+	/// Load a `Dataset` saved with [`save_binary`](Self::save_binary).
+	///
+	/// The binned data is loaded as is, including feature names, so this skips parsing and
+	/// binning the original data. Files that aren't LightGBM binary datasets, such as raw
+	/// CSV files, are reported as errors rather than parsed.
+	pub fn from_binary(file_path: impl AsRef<Path>) -> Result<Self> {
+		let file_path = file_path.as_ref();
+		let mut token = [0; BINARY_FILE_TOKEN.len()];
+		File::open(file_path)
+			.and_then(|mut file| file.read_exact(&mut token))
+			.map_err(|e| Error::io(format!("failed to read {}: {e}", file_path.display())))?;
+		if token != *BINARY_FILE_TOKEN {
+			return Err(Error::invalid_input(format!(
+				"{} is not a LightGBM binary dataset file",
+				file_path.display()
+			)));
+		}
+		Self::from_file(file_path)
+	}
+
+	/// Create a new `Dataset` from file, with parameters describing the file format.
+	///
+	/// LightGBM detects whether the file is CSV, TSV or LibSVM. The header and the label column
+	/// can be configured through [`Params::header`] and [`Params::label_column`]: the label
+	/// column is loaded as the labels of the dataset, the other columns as its features.
+	/// Parameters are [validated](Params::validate) first.
+	///
+	/// Example
+	/// ```
+	/// use lightgbm::{Dataset, LabelColumn, Params};
+	///
+	/// let params = Params::new()
+	/// 	.header(true)
+	/// 	.label_column(LabelColumn::Name("target".to_owned()));
+	/// let dataset = Dataset::from_file_with_params("test/test_header.csv", &params).unwrap();
+	/// ```
+	pub fn from_file_with_params(file_path: impl AsRef<Path>, params: &Params) -> Result<Self> {
+		let file_path_str = crate::path_to_cstring(file_path.as_ref())?;
+		params.validate()?;
+		let params = params.to_cstring()?;
 		let mut handle = std::ptr::null_mut();
 
 		lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromFile(
@@ -175,8 +509,154 @@ impl Dataset {
 		Ok(Self::new(handle))
 	}
 
+	/// Create a new `Dataset` from CSV data held in memory, e.g. received over the network,
+	/// without writing it to a file.
+	///
+	/// The CSV is parsed in Rust: fields are separated by `delimiter` and may be quoted with
+	/// `"`, doubling quotes inside quoted fields. Empty fields, `na` and `nan` are missing
+	/// values. As for [`from_file_with_params`](Self::from_file_with_params), the header and
+	/// the label column are configured through [`Params::header`] and
+	/// [`Params::label_column`], the label being the first column by default. With a header,
+	/// the other columns are named after it.
+	///
+	/// Example
+	/// ```
+	/// use lightgbm::{Dataset, LabelColumn, Params};
+	///
+	/// let csv = b"feature_1,target,feature_2\n1.0,0,0.1\n0.7,1,0.4\n";
+	/// let params = Params::new()
+	/// 	.header(true)
+	/// 	.label_column(LabelColumn::Name("target".to_owned()));
+	/// let dataset = Dataset::from_csv_bytes(csv, b',', &params).unwrap();
+	/// ```
+	pub fn from_csv_bytes(bytes: &[u8], delimiter: u8, params: &Params) -> Result<Self> {
+		let mut records = parse_csv(bytes, delimiter)?.into_iter();
+		let header = match params.get("header") {
+			Some("true") => Some(
+				records
+					.next()
+					.ok_or_else(|| Error::invalid_input("CSV data has no header"))?,
+			),
+			_ => None,
+		};
+		let label_index = match params.get("label_column") {
+			None => 0,
+			Some(label_column) => match label_column.strip_prefix("name:") {
+				Some(name) => header
+					.as_ref()
+					.and_then(|header| header.iter().position(|column| column == name))
+					.ok_or_else(|| {
+						Error::invalid_input(format!("label column {name:?} is not in the header"))
+					})?,
+				None => label_column.parse().map_err(|_| {
+					Error::invalid_input(format!("invalid label column: {label_column}"))
+				})?,
+			},
+		};
+
+		let mut n_columns = header.as_ref().map(Vec::len);
+		let mut data = Vec::new();
+		let mut label = Vec::new();
+		let mut n_rows = 0;
+		for (row, record) in records.enumerate() {
+			match n_columns {
+				None => n_columns = Some(record.len()),
+				Some(n_columns) if n_columns != record.len() => {
+					return Err(Error::invalid_input(format!(
+						"row {row} of the CSV data has {} columns, expected {n_columns}",
+						record.len(),
+					)));
+				}
+				Some(_) => {}
+			}
+			if label_index >= record.len() {
+				return Err(Error::invalid_input(format!(
+					"label column {label_index} is out of range for {} columns",
+					record.len(),
+				)));
+			}
+			for (column, field) in record.iter().enumerate() {
+				let value = match field.trim() {
+					"" => f64::NAN,
+					field if field.eq_ignore_ascii_case("na") => f64::NAN,
+					field => field.parse().map_err(|_| {
+						Error::invalid_input(format!(
+							"failed to parse {field:?} at row {row}, column {column} of the CSV \
+								data"
+						))
+					})?,
+				};
+				if column == label_index {
+					label.push(value as f32);
+				} else {
+					data.push(value);
+				}
+			}
+			n_rows += 1;
+		}
+
+		let mut dataset = Self::from_mat_with_params(&data, n_rows, &label, params)?;
+		if let Some(mut header) = header {
+			header.remove(label_index);
+			let feature_names = header.iter().map(String::as_str).collect::<Vec<_>>();
+			dataset.set_feature_names(&feature_names)?;
+		}
+		Ok(dataset)
+	}
+
+	/// Create a new `Dataset` from several files with the same columns, holding the rows of
+	/// all the files in order.
+	///
+	/// See [`from_files_with_params`](Self::from_files_with_params).
+	pub fn from_files<P: AsRef<Path>>(file_paths: &[P]) -> Result<Self> {
+		Self::from_files_with_params(file_paths, &Params::new())
+	}
+
+	/// Create a new `Dataset` from several files with the same columns, with parameters
+	/// describing the file format.
+	///
+	/// LightGBM can only read a single file, so the files are concatenated into a temporary
+	/// file that is removed once loaded, which writes the data again once. Every line of a CSV
+	/// or TSV file must have the same number of columns, across all files. If
+	/// [`Params::header`] is set, every file must start with the header line, and only the
+	/// first file's one is kept.
+	///
+	/// The side files that LightGBM loads along with a data file (`<file>.weight`,
+	/// `<file>.query`, `<file>.init` and `<file>.position`) are concatenated as well, so they
+	/// must exist for either all the files or none of them.
+	pub fn from_files_with_params<P: AsRef<Path>>(
+		file_paths: &[P],
+		params: &Params,
+	) -> Result<Self> {
+		static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+		if file_paths.is_empty() {
+			return Err(Error::invalid_input("no file to create the dataset from"));
+		}
+		let temp_path = std::env::temp_dir().join(format!(
+			"lightgbm-dataset-{}-{}.data",
+			std::process::id(),
+			TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+		));
+		let result =
+			concat_data_files(file_paths, params.get("header") == Some("true"), &temp_path)
+				.and_then(|()| concat_side_files(file_paths, &temp_path))
+				.and_then(|()| Self::from_file_with_params(&temp_path, params));
+		let _ = fs::remove_file(&temp_path);
+		for suffix in SIDE_FILE_SUFFIXES {
+			let _ = fs::remove_file(with_suffix(&temp_path, suffix));
+		}
+		result
+	}
+
 	/// Create a new `Dataset` from a polars DataFrame.
 	///
+	/// Every column but `label_column` is a feature, in the order of the columns of the
+	/// DataFrame: feature `i` is the `i`-th column once the label column is removed. The
+	/// column names are set as feature names, so models trained on this dataset record them
+	/// and [`Booster::predict_dataframe`](crate::Booster::predict_dataframe) can match
+	/// columns by name regardless of their order.
+	///
 	/// Note: the feature ```dataframe``` is required for this method
 	///
 	/// Example
@@ -208,30 +688,25 @@ impl Dataset {
 		let label_series = &dataframe.select_series(label_col_name)?[0].cast::<Float32Type>()?;
 
 		if label_series.null_count() != 0 {
-			panic!("Cannot create a dataset with null values, encountered nulls when creating the label array")
+			return Err(Error::invalid_input(
+				"Cannot create a dataset with null values, encountered nulls when creating the label array",
+			));
 		}
 
 		dataframe.drop_in_place(label_col_name)?;
 
-		let mut label_values = Vec::with_capacity(m);
-
-		let label_values_ca = label_series.unpack::<Float32Type>()?;
-
-		label_values_ca
+		let label_values = label_series
+			.unpack::<Float32Type>()?
 			.into_no_null_iter()
-			.enumerate()
-			.for_each(|(_row_idx, val)| {
-				label_values.push(val);
-			});
-
-		let mut feature_values = Vec::with_capacity(m);
-		for _i in 0..m {
-			feature_values.push(Vec::with_capacity(n));
-		}
+			.collect::<Vec<_>>();
 
-		for (_col_idx, series) in dataframe.get_columns().iter().enumerate() {
+		let n_features = n - 1;
+		let mut feature_values = vec![0_f64; m * n_features];
+		for (col_idx, series) in dataframe.get_columns().iter().enumerate() {
 			if series.null_count() != 0 {
-				panic!("Cannot create a dataset with null values, encountered nulls when creating the features array")
+				return Err(Error::invalid_input(
+					"Cannot create a dataset with null values, encountered nulls when creating the features array",
+				));
 			}
 
 			let series = series.cast::<Float64Type>()?;
@@ -239,9 +714,160 @@ impl Dataset {
 
 			ca.into_no_null_iter()
 				.enumerate()
-				.for_each(|(row_idx, val)| feature_values[row_idx].push(val));
+				.for_each(|(row_idx, val)| feature_values[row_idx * n_features + col_idx] = val);
+		}
+		Self::from_mat_named(
+			&feature_values,
+			m,
+			&label_values,
+			&dataframe.get_column_names(),
+		)
+	}
+
+	/// Create a new ranking `Dataset` from a polars DataFrame holding the label, the query id
+	/// and optionally the weight of each record.
+	///
+	/// Records must be sorted by query id, as LightGBM requires the records of each query to
+	/// be contiguous: groups are the runs of consecutive equal ids in `group_column`. Every
+	/// other column is a feature, as in [`from_dataframe`](Self::from_dataframe).
+	///
+	/// Note: the feature ```dataframe``` is required for this method
+	#[cfg(feature = "dataframe")]
+	pub fn from_dataframe_ranking(
+		mut dataframe: DataFrame,
+		label_column: String,
+		group_column: &str,
+		weight_column: Option<&str>,
+	) -> Result<Self> {
+		let group_series = &dataframe.select_series(group_column)?[0].cast::<Int64Type>()?;
+		if group_series.null_count() != 0 {
+			return Err(Error::invalid_input(
+				"Cannot create a dataset with null values, encountered nulls when creating the group array",
+			));
+		}
+		dataframe.drop_in_place(group_column)?;
+		let mut group = Vec::<i32>::new();
+		let mut previous_id = None;
+		for id in group_series.unpack::<Int64Type>()?.into_no_null_iter() {
+			match previous_id {
+				Some(previous_id) if id == previous_id => *group.last_mut().unwrap() += 1,
+				Some(previous_id) if id < previous_id => {
+					return Err(Error::invalid_input(format!(
+						"{group_column} should be sorted, got {id} after {previous_id}"
+					)));
+				}
+				_ => group.push(1),
+			}
+			previous_id = Some(id);
+		}
+
+		let weight = match weight_column {
+			Some(weight_column) => {
+				let weight_series =
+					&dataframe.select_series(weight_column)?[0].cast::<Float32Type>()?;
+				if weight_series.null_count() != 0 {
+					return Err(Error::invalid_input(
+						"Cannot create a dataset with null values, encountered nulls when creating the weight array",
+					));
+				}
+				dataframe.drop_in_place(weight_column)?;
+				let weight = weight_series
+					.unpack::<Float32Type>()?
+					.into_no_null_iter()
+					.collect::<Vec<_>>();
+				Some(weight)
+			}
+			None => None,
+		};
+
+		let mut dataset = Self::from_dataframe(dataframe, label_column)?;
+		let mut fields = DatasetFields::new().group(&group);
+		if let Some(weight) = &weight {
+			fields = fields.weight(weight);
+		}
+		dataset.set_fields(fields)?;
+		Ok(dataset)
+	}
+
+	/// Create a new `Dataset` from a polars DataFrame, encoding its string columns.
+	///
+	/// Like [`from_dataframe`](Self::from_dataframe), every column but `label_column` is a
+	/// feature, but `Utf8` columns are encoded as `categorical_as` says instead of being
+	/// rejected. Categories are the distinct values of the column in sorted order, so the
+	/// same values always get the same codes regardless of the order of the rows:
+	/// - [`CategoricalEncoding::Native`]: the column holds the index of each value's
+	///   category, and is passed to LightGBM as a categorical feature
+	/// - [`CategoricalEncoding::OneHot`]: the column is replaced by one `0`/`1` column per
+	///   category, named `{column}={category}`
+	///
+	/// The returned [`CategoricalMapping`] records the categories, so that data to predict
+	/// can be [encoded](CategoricalMapping::encode) the same way.
+	///
+	/// Note: the feature ```dataframe``` is required for this method
+	#[cfg(feature = "dataframe")]
+	pub fn from_dataframe_auto(
+		mut dataframe: DataFrame,
+		label_column: String,
+		categorical_as: CategoricalEncoding,
+	) -> Result<(Self, CategoricalMapping)> {
+		let label_col_name = label_column.as_str();
+		let label_series = &dataframe.select_series(label_col_name)?[0].cast::<Float32Type>()?;
+		if label_series.null_count() != 0 {
+			return Err(Error::invalid_input(
+				"Cannot create a dataset with null values, encountered nulls when creating the label array",
+			));
+		}
+		dataframe.drop_in_place(label_col_name)?;
+		let label_values = label_series
+			.unpack::<Float32Type>()?
+			.into_no_null_iter()
+			.collect::<Vec<_>>();
+		let n_rows = dataframe.height();
+
+		let mut mapping = CategoricalMapping {
+			encoding: categorical_as,
+			categories: Vec::new(),
+		};
+		for series in dataframe.get_columns() {
+			if series.dtype() == &DataType::Utf8 {
+				let mut categories: Vec<String> = series
+					.utf8()?
+					.into_no_null_iter()
+					.map(str::to_owned)
+					.collect();
+				categories.sort_unstable();
+				categories.dedup();
+				mapping
+					.categories
+					.push((series.name().to_owned(), categories));
+			}
+		}
+		let columns = mapping.encode_columns(&dataframe)?;
+
+		let n_features = columns.len();
+		let mut feature_values = vec![0_f64; n_rows * n_features];
+		for (col_idx, (_, values)) in columns.iter().enumerate() {
+			for (row_idx, &val) in values.iter().enumerate() {
+				feature_values[row_idx * n_features + col_idx] = val;
+			}
+		}
+		let mut params = Params::new();
+		if categorical_as == CategoricalEncoding::Native {
+			let categorical_features = columns
+				.iter()
+				.enumerate()
+				.filter(|(_, (name, _))| mapping.categories(name).is_some())
+				.map(|(col_idx, _)| col_idx.to_string())
+				.collect::<Vec<_>>();
+			if !categorical_features.is_empty() {
+				params = params.set("categorical_feature", categorical_features.join(","));
+			}
 		}
-		Self::from_mat(feature_values, label_values)
+		let mut dataset =
+			Self::from_mat_with_params(&feature_values, n_rows, &label_values, &params)?;
+		let feature_names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+		dataset.set_feature_names(&feature_names)?;
+		Ok((dataset, mapping))
 	}
 
 	pub fn n_rows(&self) -> Result<usize> {
@@ -252,7 +878,7 @@ impl Dataset {
 		))?;
 		result
 			.try_into()
-			.map_err(|_| Error::new("dataset length negative"))
+			.map_err(|_| Error::ffi("dataset length negative"))
 	}
 
 	pub fn n_features(&self) -> Result<usize> {
@@ -263,79 +889,1207 @@ impl Dataset {
 		))?;
 		result
 			.try_into()
-			.map_err(|_| Error::new("feature count negative"))
+			.map_err(|_| Error::ffi("feature count negative"))
 	}
 
-	pub fn set_weights(&mut self, weights: &[f32]) -> Result<()> {
-		let n_rows = self.n_rows()?;
-		if n_rows != weights.len() {
-			return Err(Error::new(format!(
-				"got {} weights, but dataset has {} records",
-				weights.len(),
-				n_rows
+	/// Get the number of bins LightGBM split the values of `feature` into.
+	pub fn num_bins(&self, feature: usize) -> Result<usize> {
+		let n_features = self.n_features()?;
+		if feature >= n_features {
+			return Err(Error::invalid_input(format!(
+				"feature {feature} is out of bounds, the dataset has {n_features} features"
+			)));
+		}
+		let mut result = 0_i32;
+		lgbm_call!(lightgbm_sys::LGBM_DatasetGetFeatureNumBin(
+			self.handle,
+			// feature < n_features, which fits into an i32
+			feature as i32,
+			&mut result
+		))?;
+		result
+			.try_into()
+			.map_err(|_| Error::ffi("number of bins negative"))
+	}
+
+	/// Get the number of records and the number of features of the dataset, as
+	/// `(n_rows, n_features)`.
+	pub fn shape(&self) -> Result<(usize, usize)> {
+		Ok((self.n_rows()?, self.n_features()?))
+	}
+
+	/// Randomly split the rows into a training and a validation dataset, with about
+	/// `valid_fraction` of the rows in the validation dataset.
+	///
+	/// The split only depends on `seed` and the number of rows, so it is reproducible. Both
+	/// datasets share the binning of this dataset, and keep the labels, weights and init
+	/// scores of their rows.
+	pub fn train_valid_split(&self, valid_fraction: f64, seed: u64) -> Result<(Dataset, Dataset)> {
+		if valid_fraction.is_nan() || valid_fraction <= 0. || valid_fraction >= 1. {
+			return Err(Error::invalid_input(format!(
+				"valid_fraction should be in (0, 1), got {valid_fraction}"
+			)));
+		}
+		let n_rows: i32 = self
+			.n_rows()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		if n_rows < 2 {
+			return Err(Error::invalid_input(format!(
+				"can't split a dataset with {n_rows} rows"
+			)));
+		}
+		let n_valid = ((n_rows as f64 * valid_fraction).round() as i32).clamp(1, n_rows - 1);
+
+		// Fisher-Yates shuffle
+		let mut indices: Vec<i32> = (0..n_rows).collect();
+		let mut rng = SplitMix64(seed);
+		for i in (1..indices.len()).rev() {
+			let j = (rng.next() % (i as u64 + 1)) as usize;
+			indices.swap(i, j);
+		}
+		let (valid_indices, train_indices) = indices.split_at_mut(n_valid as usize);
+		valid_indices.sort_unstable();
+		train_indices.sort_unstable();
+
+		Ok((self.subset(train_indices)?, self.subset(valid_indices)?))
+	}
+
+	/// Create a dataset with the given rows of this one, which must be sorted.
+	pub(crate) fn subset(&self, used_row_indices: &[i32]) -> Result<Dataset> {
+		let len = used_row_indices
+			.len()
+			.try_into()
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		let params_cstring =
+			CString::new("").map_err(|e| Error::from_other("failed to make cstring", e))?;
+		let mut handle = std::ptr::null_mut();
+		lgbm_call!(lightgbm_sys::LGBM_DatasetGetSubset(
+			self.handle,
+			used_row_indices.as_ptr(),
+			len,
+			params_cstring.as_ptr() as *const c_char,
+			&mut handle
+		))?;
+		Ok(Dataset::new(handle))
+	}
+
+	/// Get a field of the dataset (label, weight...).
+	fn get_field<T: FieldType>(&self, field_name: &str) -> Result<Vec<T>> {
+		let field_name_str =
+			CString::new(field_name).map_err(|e| Error::from_other("failed to make cstring", e))?;
+		let mut out_len = 0_i32;
+		let mut out_ptr = std::ptr::null();
+		let mut out_type = 0_i32;
+		lgbm_call!(lightgbm_sys::LGBM_DatasetGetField(
+			self.handle,
+			field_name_str.as_ptr() as *const c_char,
+			&mut out_len,
+			&mut out_ptr,
+			&mut out_type
+		))?;
+		let len = out_len.try_into().map_err(|_| {
+			Error::ffi(format!("{field_name} length returned by C API is negative"))
+		})?;
+		if len == 0 {
+			return Ok(Vec::new());
+		}
+		if out_type != T::C_API_DTYPE {
+			return Err(Error::ffi(format!(
+				"unexpected {field_name} type returned by C API: {out_type}"
 			)));
 		}
-		let field_name = CString::new("weight").unwrap();
-		let len = weights
+		// The field data is owned by the dataset, so we copy it out
+		Ok(unsafe { std::slice::from_raw_parts(out_ptr as *const T, len) }.to_vec())
+	}
+
+	/// Set a field of the dataset (label, weight...).
+	fn set_field<T: FieldType>(&mut self, field_name: &str, values: &[T]) -> Result<()> {
+		let field_name_str =
+			CString::new(field_name).map_err(|e| Error::from_other("failed to make cstring", e))?;
+		let len = values
 			.len()
 			.try_into()
-			.map_err(|_| Error::new("weights len doesn't fit into an i32"))?;
+			.map_err(|_| Error::conversion(format!("{field_name} len doesn't fit into an i32")))?;
 		lgbm_call!(lightgbm_sys::LGBM_DatasetSetField(
 			self.handle,
-			field_name.as_ptr() as *const c_char,
-			weights.as_ptr() as *const c_void,
+			field_name_str.as_ptr() as *const c_char,
+			values.as_ptr() as *const c_void,
 			len,
-			lightgbm_sys::C_API_DTYPE_FLOAT32 as i32,
+			T::C_API_DTYPE,
 		))?;
 		Ok(())
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-	fn read_train_file() -> Result<Dataset> {
-		Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+	/// Set the `f32` field `field_name` of the dataset, with one value per record.
+	///
+	/// This is a low-level escape hatch for fields that don't have a typed setter yet, e.g.
+	/// fields added by newer LightGBM versions. Prefer the typed setters such as
+	/// [`set_weights`](Self::set_weights) when they exist. LightGBM reports unknown fields and
+	/// fields of another type as errors.
+	pub fn set_field_f32(&mut self, field_name: &str, values: &[f32]) -> Result<()> {
+		self.check_field_len(field_name, values.len())?;
+		self.set_field(field_name, values)
 	}
 
-	#[test]
-	fn read_file() {
-		assert!(read_train_file().is_ok());
+	/// Set the `i32` field `field_name` of the dataset, with one value per record, e.g.
+	/// `"position"` for ranking with LightGBM 4.1 or later.
+	///
+	/// Like [`set_field_f32`](Self::set_field_f32), this is a low-level escape hatch.
+	/// `"group"` doesn't have one value per record, use [`set_fields`](Self::set_fields) to set
+	/// it.
+	pub fn set_field_i32(&mut self, field_name: &str, values: &[i32]) -> Result<()> {
+		self.check_field_len(field_name, values.len())?;
+		self.set_field(field_name, values)
 	}
 
-	#[test]
-	fn from_mat() {
-		let data = &[
-			[1.0, 0.1, 0.2, 0.1],
-			[0.7, 0.4, 0.5, 0.1],
-			[0.9, 0.8, 0.5, 0.1],
-			[0.2, 0.2, 0.8, 0.7],
-			[0.1, 0.7, 1.0, 0.9],
-		];
-		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
-		let dataset = Dataset::from_mat(
-			&data.iter().flatten().copied().collect::<Vec<_>>(),
-			data.len(),
-			label,
-		);
-		assert!(dataset.is_ok());
+	fn check_field_len(&self, field_name: &str, len: usize) -> Result<()> {
+		let n_rows = self.n_rows()?;
+		if n_rows != len {
+			return Err(Error::invalid_input(format!(
+				"got {len} {field_name} values, but dataset has {n_rows} records"
+			)));
+		}
+		Ok(())
 	}
 
-	#[cfg(feature = "dataframe")]
-	#[test]
-	fn from_dataframe() {
-		use polars::df;
-		let df: DataFrame = df![
-			"feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
+	/// Get the labels of the dataset.
+	pub fn get_label(&self) -> Result<Vec<f32>> {
+		self.get_field("label")
+	}
+
+	/// Set the labels of the dataset, replacing those it was created with.
+	///
+	/// This doesn't bin the features again, so relabeling a dataset is much cheaper than
+	/// creating a new one. There must be exactly one label per record.
+	pub fn set_label(&mut self, label: &[f32]) -> Result<()> {
+		let n_rows = self.n_rows()?;
+		if n_rows != label.len() {
+			return Err(Error::invalid_input(format!(
+				"got {} labels, but dataset has {} records",
+				label.len(),
+				n_rows
+			)));
+		}
+		self.set_field("label", label)
+	}
+
+	pub fn set_weights(&mut self, weights: &[f32]) -> Result<()> {
+		let n_rows = self.n_rows()?;
+		if n_rows != weights.len() {
+			return Err(Error::invalid_input(format!(
+				"got {} weights, but dataset has {} records",
+				weights.len(),
+				n_rows
+			)));
+		}
+		self.set_field("weight", weights)
+	}
+
+	/// Weigh each record by the weight of its class, for imbalanced classification.
+	///
+	/// Labels must be class indices: `class_weights[c]` is the weight of the records whose
+	/// label is `c`, so there must be a weight for every class present in the labels. Classes
+	/// that have no records (e.g. the last of `num_class` classes) may be given a weight, which
+	/// is then unused. This sets the per-record weights (see [`set_weights`](Self::set_weights))
+	/// accordingly.
+	pub fn set_class_weights(&mut self, class_weights: &[f32]) -> Result<()> {
+		let labels = self.get_label()?;
+		let weights = labels
+			.iter()
+			.copied()
+			.enumerate()
+			.map(|(row, label)| {
+				let is_class_index = label >= 0. && label.fract() == 0.;
+				match class_weights.get(label as usize) {
+					Some(&weight) if is_class_index => Ok(weight),
+					_ => Err(Error::invalid_input(format!(
+						"label {label} at row {row} is not a class index in 0..{}",
+						class_weights.len()
+					))),
+				}
+			})
+			.collect::<Result<Vec<_>>>()?;
+		self.set_weights(&weights)
+	}
+
+	/// Set the initial score of each record, from which boosting starts.
+	///
+	/// For multiclass models there is one score per record and class, laid out class-major
+	/// (all records' scores for class 0, then for class 1...).
+	pub fn set_init_score(&mut self, init_score: &[f64]) -> Result<()> {
+		let n_rows = self.n_rows()?;
+		if n_rows == 0 || init_score.is_empty() || !init_score.len().is_multiple_of(n_rows) {
+			return Err(Error::invalid_input(format!(
+				"got {} init scores, but dataset has {} records",
+				init_score.len(),
+				n_rows
+			)));
+		}
+		self.set_field("init_score", init_score)
+	}
+
+	/// Get the initial score of each record, or `None` if it hasn't been set.
+	pub fn get_init_score(&self) -> Result<Option<Vec<f64>>> {
+		let init_score = self.get_field("init_score")?;
+		Ok((!init_score.is_empty()).then_some(init_score))
+	}
+
+	/// Set several fields of the dataset at once.
+	///
+	/// All fields are validated against the number of rows before any of them is set, so an
+	/// invalid field leaves the dataset unchanged.
+	///
+	/// Example
+	/// ```
+	/// use lightgbm::{Dataset, DatasetFields};
+	///
+	/// let data = &[[1.0, 0.1], [0.7, 0.4], [0.9, 0.8], [0.2, 0.2], [0.1, 0.7]];
+	/// let mut dataset = Dataset::from_mat(
+	/// 	&data.iter().flatten().copied().collect::<Vec<_>>(),
+	/// 	data.len(),
+	/// 	&[0.0; 5],
+	/// )
+	/// .unwrap();
+	/// dataset
+	/// 	.set_fields(
+	/// 		DatasetFields::new()
+	/// 			.label(&[2.0, 1.0, 0.0, 1.0, 0.0])
+	/// 			.group(&[2, 3]),
+	/// 	)
+	/// 	.unwrap();
+	/// ```
+	pub fn set_fields(&mut self, fields: DatasetFields) -> Result<()> {
+		let n_rows = self.n_rows()?;
+		let check_len = |name: &str, len: usize| {
+			if len != n_rows {
+				return Err(Error::invalid_input(format!(
+					"got {len} {name} values, but dataset has {n_rows} records"
+				)));
+			}
+			Ok(())
+		};
+		if let Some(label) = fields.label {
+			check_len("label", label.len())?;
+			if let Some(row) = label.iter().position(|label| !label.is_finite()) {
+				return Err(Error::invalid_input(format!(
+					"label at row {row} is not finite"
+				)));
+			}
+		}
+		if let Some(weight) = fields.weight {
+			check_len("weight", weight.len())?;
+			if let Some(row) = weight
+				.iter()
+				.position(|weight| !(weight.is_finite() && *weight >= 0.))
+			{
+				return Err(Error::invalid_input(format!(
+					"weight at row {row} is not a non-negative number"
+				)));
+			}
+		}
+		if let Some(group) = fields.group {
+			if group.iter().any(|&size| size < 0) {
+				return Err(Error::invalid_input("group sizes should be non-negative"));
+			}
+			let group_sum = group.iter().map(|&size| size as u64).sum::<u64>();
+			if group_sum != n_rows as u64 {
+				return Err(Error::invalid_input(format!(
+					"group sizes sum to {group_sum}, but dataset has {n_rows} records"
+				)));
+			}
+		}
+		if let Some(init_score) = fields.init_score {
+			if n_rows == 0 || init_score.is_empty() || !init_score.len().is_multiple_of(n_rows) {
+				return Err(Error::invalid_input(format!(
+					"got {} init scores, but dataset has {n_rows} records",
+					init_score.len(),
+				)));
+			}
+		}
+		if let Some(position) = fields.position {
+			check_len("position", position.len())?;
+		}
+
+		if let Some(label) = fields.label {
+			self.set_field("label", label)?;
+		}
+		if let Some(weight) = fields.weight {
+			self.set_field("weight", weight)?;
+		}
+		if let Some(group) = fields.group {
+			self.set_field("group", group)?;
+		}
+		if let Some(init_score) = fields.init_score {
+			self.set_field("init_score", init_score)?;
+		}
+		if let Some(position) = fields.position {
+			self.set_field("position", position)?;
+		}
+		Ok(())
+	}
+
+	/// Fill `n_rows` records of a dataset created with
+	/// [`from_sampled_columns`](Self::from_sampled_columns), starting at record `start_row`.
+	///
+	/// `data` holds the `n_rows` records in row-major order. The values are binned with the
+	/// bins computed from the sample.
+	pub fn push_rows(&mut self, data: &[f64], n_rows: usize, start_row: usize) -> Result<()> {
+		let (nrow, ncol) = mat_shape(data.len(), n_rows)?;
+		let n_features = self.n_features()?;
+		if n_rows > 0 && data.len() / n_rows != n_features {
+			return Err(Error::invalid_input(format!(
+				"got rows of {} values, but dataset has {n_features} features",
+				data.len() / n_rows
+			)));
+		}
+		let total_n_rows = self.n_rows()?;
+		if start_row
+			.checked_add(n_rows)
+			.filter(|&end_row| end_row <= total_n_rows)
+			.is_none()
+		{
+			return Err(Error::invalid_input(format!(
+				"can't push {n_rows} rows from row {start_row}, dataset has {total_n_rows} records"
+			)));
+		}
+		let start_row = start_row
+			.try_into()
+			.map_err(|_| Error::conversion("start row doesn't fit into an i32"))?;
+		lgbm_call!(lightgbm_sys::LGBM_DatasetPushRows(
+			self.handle,
+			data.as_ptr() as *const c_void,
+			lightgbm_sys::C_API_DTYPE_FLOAT64,
+			nrow,
+			ncol,
+			start_row
+		))?;
+		Ok(())
+	}
+
+	/// Save the dataset, as binned by LightGBM, to a binary file, which can be loaded back
+	/// with [`from_binary`](Self::from_binary).
+	///
+	/// This allows binning the data once and training several models on it, possibly with
+	/// different parameters. Parameters that affect binning (e.g. `max_bin`) are those the
+	/// dataset was created with.
+	pub fn save_binary(&self, filename: impl AsRef<Path>) -> Result<()> {
+		let filename = filename.as_ref();
+		if filename.as_os_str().is_empty() {
+			return Err(Error::invalid_input("filename to save dataset to is empty"));
+		}
+		let filename_str = crate::path_to_cstring(filename)?;
+		lgbm_call!(lightgbm_sys::LGBM_DatasetSaveBinary(
+			self.handle,
+			filename_str.as_ptr() as *const c_char
+		))?;
+		Ok(())
+	}
+
+	/// Dump the dataset, as binned by LightGBM, to a human-readable text file.
+	///
+	/// This is meant for debugging, the format is not meant to be parsed back.
+	pub fn dump_text(&self, filename: impl AsRef<Path>) -> Result<()> {
+		let filename = filename.as_ref();
+		if filename.as_os_str().is_empty() {
+			return Err(Error::invalid_input("filename to dump dataset to is empty"));
+		}
+		let filename_str = crate::path_to_cstring(filename)?;
+		lgbm_call!(lightgbm_sys::LGBM_DatasetDumpText(
+			self.handle,
+			filename_str.as_ptr() as *const c_char
+		))?;
+		Ok(())
+	}
+
+	/// Get the feature names, which are `Column_<index>` unless they were
+	/// [set](Self::set_feature_names).
+	pub fn feature_names(&self) -> Result<Vec<String>> {
+		let len: i32 = self
+			.n_features()?
+			.try_into()
+			.map_err(|_| Error::conversion("number of features doesn't fit into an i32"))?;
+		let mut string_size = 64;
+		loop {
+			let mut strings = (0..len)
+				.map(|_| vec![0_u8; string_size])
+				.collect::<Vec<_>>();
+			let mut out_strs = strings
+				.iter_mut()
+				.map(|v| v.as_mut_ptr() as *mut c_char)
+				.collect::<Vec<_>>();
+			let mut num_strings = 0;
+			let mut actual_string_len = 0;
+			lgbm_call!(lightgbm_sys::LGBM_DatasetGetFeatureNames(
+				self.handle,
+				len,
+				&mut num_strings,
+				string_size,
+				&mut actual_string_len,
+				out_strs.as_mut_ptr()
+			))?;
+			// If the string size was too small, try again with the actual size
+			if actual_string_len > string_size {
+				string_size = actual_string_len;
+				continue;
+			}
+			let num_strings: usize = num_strings
+				.try_into()
+				.map_err(|_| Error::ffi("negative number of strings returned by LGBM C API"))?;
+			return strings
+				.iter()
+				.take(num_strings)
+				.map(|s| {
+					CStr::from_bytes_until_nul(s)
+						.map_err(|e| Error::from_other("unterminated feature name", e))
+						.map(|name| name.to_string_lossy().into_owned())
+				})
+				.collect();
+		}
+	}
+
+	/// Set the feature names, which are then recorded by models trained on this dataset.
+	///
+	/// There must be exactly one name per feature, and names must be unique: duplicates are
+	/// reported as an error listing them.
+	pub fn set_feature_names(&mut self, feature_names: &[&str]) -> Result<()> {
+		let n_features = self.n_features()?;
+		if n_features != feature_names.len() {
+			return Err(Error::invalid_input(format!(
+				"got {} feature names, but dataset has {} features",
+				feature_names.len(),
+				n_features
+			)));
+		}
+		let mut seen = HashSet::new();
+		let mut duplicates = Vec::new();
+		for &name in feature_names {
+			if !seen.insert(name) && !duplicates.contains(&name) {
+				duplicates.push(name);
+			}
+		}
+		if !duplicates.is_empty() {
+			return Err(Error::invalid_input(format!(
+				"feature names should be unique, got duplicates: {}",
+				duplicates.join(", ")
+			)));
+		}
+		let feature_names = feature_names
+			.iter()
+			.map(|&name| {
+				CString::new(name).map_err(|e| Error::from_other("failed to make cstring", e))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		let mut feature_name_ptrs = feature_names
+			.iter()
+			.map(|name| name.as_ptr() as *const c_char)
+			.collect::<Vec<_>>();
+		let len = feature_name_ptrs
+			.len()
+			.try_into()
+			.map_err(|_| Error::conversion("feature names len doesn't fit into an i32"))?;
+		lgbm_call!(lightgbm_sys::LGBM_DatasetSetFeatureNames(
+			self.handle,
+			feature_name_ptrs.as_mut_ptr(),
+			len,
+		))?;
+		Ok(())
+	}
+}
+
+/// Token LightGBM binary dataset files start with.
+const BINARY_FILE_TOKEN: &[u8] = b"______LightGBM_Binary_File_Token______\n";
+
+/// Fields to set on a dataset at once, see [`Dataset::set_fields`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DatasetFields<'a> {
+	label: Option<&'a [f32]>,
+	weight: Option<&'a [f32]>,
+	group: Option<&'a [i32]>,
+	init_score: Option<&'a [f64]>,
+	position: Option<&'a [i32]>,
+}
+
+impl<'a> DatasetFields<'a> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Label of each record.
+	pub fn label(mut self, label: &'a [f32]) -> Self {
+		self.label = Some(label);
+		self
+	}
+
+	/// Weight of each record, see [`Dataset::set_weights`].
+	pub fn weight(mut self, weight: &'a [f32]) -> Self {
+		self.weight = Some(weight);
+		self
+	}
+
+	/// Size of each query group for ranking, records of a group being contiguous. The sizes
+	/// must sum up to the number of records.
+	pub fn group(mut self, group: &'a [i32]) -> Self {
+		self.group = Some(group);
+		self
+	}
+
+	/// Initial score of each record, see [`Dataset::set_init_score`].
+	pub fn init_score(mut self, init_score: &'a [f64]) -> Self {
+		self.init_score = Some(init_score);
+		self
+	}
+
+	/// Position of each record when it was displayed, used to debias ranking from position
+	/// bias.
+	pub fn position(mut self, position: &'a [i32]) -> Self {
+		self.position = Some(position);
+		self
+	}
+}
+
+/// Concatenate data files into `output`, checking that CSV and TSV lines all have the same
+/// number of columns.
+fn concat_data_files<P: AsRef<Path>>(file_paths: &[P], header: bool, output: &Path) -> Result<()> {
+	let io_error = |path: &dyn std::fmt::Debug, e: std::io::Error| {
+		Error::io(format!("failed to concatenate {path:?}: {e}"))
+	};
+	let mut writer = BufWriter::new(File::create(output).map_err(|e| io_error(&output, e))?);
+	let mut n_columns = None;
+	for (file_idx, file_path) in file_paths.iter().enumerate() {
+		let file_path = file_path.as_ref();
+		let reader = BufReader::new(File::open(file_path).map_err(|e| io_error(&file_path, e))?);
+		for (line_idx, line) in reader.lines().enumerate() {
+			let line = line.map_err(|e| io_error(&file_path, e))?;
+			let is_header = header && line_idx == 0;
+			if is_header && file_idx != 0 {
+				continue;
+			}
+			// LibSVM lines have a varying number of `index:value` columns
+			if !is_header && !line.trim().is_empty() && !line.contains(':') {
+				let delimiter = [b'\t', b',']
+					.into_iter()
+					.find(|&d| line.as_bytes().contains(&d));
+				let line_columns = match delimiter {
+					Some(delimiter) => line.split(delimiter as char).count(),
+					None => line.split_whitespace().count(),
+				};
+				match n_columns {
+					None => n_columns = Some(line_columns),
+					Some(n_columns) if n_columns != line_columns => {
+						return Err(Error::invalid_input(format!(
+							"line {} of {} has {line_columns} columns, \
+								expected {n_columns} like the previous lines",
+							line_idx + 1,
+							file_path.display(),
+						)));
+					}
+					Some(_) => {}
+				}
+			}
+			writeln!(writer, "{line}").map_err(|e| io_error(&output, e))?;
+		}
+	}
+	writer.flush().map_err(|e| io_error(&output, e))
+}
+
+/// Split CSV data into records of fields, unquoting quoted fields and skipping blank lines.
+fn parse_csv(bytes: &[u8], delimiter: u8) -> Result<Vec<Vec<String>>> {
+	if !delimiter.is_ascii() || matches!(delimiter, b'"' | b'\n' | b'\r') {
+		return Err(Error::invalid_input(format!(
+			"invalid CSV delimiter: {:?}",
+			delimiter as char
+		)));
+	}
+	let delimiter = delimiter as char;
+	let text = std::str::from_utf8(bytes)
+		.map_err(|e| Error::from_other("CSV data is not valid UTF-8", e))?;
+	let mut records = Vec::new();
+	let mut record = Vec::new();
+	let mut field = String::new();
+	let mut in_quotes = false;
+	let mut chars = text.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'"' if in_quotes => {
+				if chars.peek() == Some(&'"') {
+					chars.next();
+					field.push('"');
+				} else {
+					in_quotes = false;
+				}
+			}
+			_ if in_quotes => field.push(c),
+			'"' if field.trim().is_empty() => {
+				field.clear();
+				in_quotes = true;
+			}
+			'\n' | '\r' => {
+				if c == '\r' && chars.peek() == Some(&'\n') {
+					chars.next();
+				}
+				record.push(std::mem::take(&mut field));
+				let record = std::mem::take(&mut record);
+				if record.len() > 1 || !record[0].trim().is_empty() {
+					records.push(record);
+				}
+			}
+			_ if c == delimiter => record.push(std::mem::take(&mut field)),
+			_ => field.push(c),
+		}
+	}
+	if in_quotes {
+		return Err(Error::invalid_input("CSV data ends inside a quoted field"));
+	}
+	if !record.is_empty() || !field.trim().is_empty() {
+		record.push(field);
+		records.push(record);
+	}
+	Ok(records)
+}
+
+/// Suffixes of the files that LightGBM loads along with a data file, holding its metadata
+const SIDE_FILE_SUFFIXES: [&str; 4] = [".weight", ".query", ".init", ".position"];
+
+/// `path` with `suffix` appended to its file name.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+	let mut path = path.as_os_str().to_owned();
+	path.push(suffix);
+	PathBuf::from(path)
+}
+
+/// Concatenate the side files of `file_paths` next to `output`, checking that each kind of
+/// side file exists for all the files or for none of them.
+fn concat_side_files<P: AsRef<Path>>(file_paths: &[P], output: &Path) -> Result<()> {
+	for suffix in SIDE_FILE_SUFFIXES {
+		let side_files: Vec<PathBuf> = file_paths
+			.iter()
+			.map(|file_path| with_suffix(file_path.as_ref(), suffix))
+			.collect();
+		let (existing, missing): (Vec<&PathBuf>, Vec<&PathBuf>) =
+			side_files.iter().partition(|side_file| side_file.exists());
+		match (existing.first(), missing.first()) {
+			(None, _) => continue,
+			(Some(existing), Some(missing)) => {
+				return Err(Error::invalid_input(format!(
+					"{} exists but {} doesn't, side files must exist for all the files or none",
+					existing.display(),
+					missing.display(),
+				)));
+			}
+			(Some(_), None) => {}
+		}
+		let output = with_suffix(output, suffix);
+		let io_error = |path: &Path, e: std::io::Error| {
+			Error::io(format!("failed to concatenate {}: {e}", path.display()))
+		};
+		let mut writer = BufWriter::new(File::create(&output).map_err(|e| io_error(&output, e))?);
+		for side_file in &side_files {
+			let reader = BufReader::new(File::open(side_file).map_err(|e| io_error(side_file, e))?);
+			for line in reader.lines() {
+				let line = line.map_err(|e| io_error(side_file, e))?;
+				writeln!(writer, "{line}").map_err(|e| io_error(&output, e))?;
+			}
+		}
+		writer.flush().map_err(|e| io_error(&output, e))?;
+	}
+	Ok(())
+}
+
+/// Number of rows and columns of a row-major matrix of `data_len` values, as passed to
+/// LightGBM.
+fn mat_shape(data_len: usize, n_rows: usize) -> Result<(i32, i32)> {
+	if n_rows == 0 {
+		if data_len != 0 {
+			return Err(Error::invalid_input(format!(
+				"got {data_len} values but n_rows is 0"
+			)));
+		}
+		return Ok((0, 0));
+	}
+	if !data_len.is_multiple_of(n_rows) {
+		return Err(Error::invalid_input(format!(
+			"data len is not multiple of n_rows ({n_rows}), but all rows \
+				should have the same number of features",
+		)));
+	}
+	let nrow = n_rows
+		.try_into()
+		.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+	let ncol = (data_len / n_rows)
+		.try_into()
+		.map_err(|_| Error::conversion("number of columns doesn't fit into an i32"))?;
+	Ok((nrow, ncol))
+}
+
+/// How [`Dataset::from_dataframe_auto`] encodes string columns.
+///
+/// Note: the feature ```dataframe``` is required for this type
+#[cfg(feature = "dataframe")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CategoricalEncoding {
+	/// Integer codes, handled by LightGBM as a categorical feature
+	Native,
+	/// One indicator feature per category
+	OneHot,
+}
+
+/// Categories of the string columns of a DataFrame, as encoded by
+/// [`Dataset::from_dataframe_auto`].
+///
+/// Note: the feature ```dataframe``` is required for this type
+#[cfg(feature = "dataframe")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CategoricalMapping {
+	encoding: CategoricalEncoding,
+	/// Sorted categories of each string column, in the order of the columns
+	categories: Vec<(String, Vec<String>)>,
+}
+
+#[cfg(feature = "dataframe")]
+impl CategoricalMapping {
+	/// How string columns are encoded.
+	pub fn encoding(&self) -> CategoricalEncoding {
+		self.encoding
+	}
+
+	/// Sorted categories of the string column `column`, if it was encoded.
+	pub fn categories(&self, column: &str) -> Option<&[String]> {
+		self.categories
+			.iter()
+			.find(|(name, _)| name == column)
+			.map(|(_, categories)| categories.as_slice())
+	}
+
+	/// Encode the string columns of `dataframe` like the training DataFrame, e.g. before
+	/// [`Booster::predict_dataframe`](crate::Booster::predict_dataframe).
+	///
+	/// Values get the codes or indicator columns of the training categories, whatever the
+	/// categories present in `dataframe`. Values that weren't seen in training are encoded as
+	/// missing (NaN) with [`CategoricalEncoding::Native`], and with all indicators set to `0`
+	/// with [`CategoricalEncoding::OneHot`]. Other columns are kept as is, cast to `f64`.
+	pub fn encode(&self, dataframe: &DataFrame) -> Result<DataFrame> {
+		let columns: Vec<Series> = self
+			.encode_columns(dataframe)?
+			.into_iter()
+			.map(|(name, values)| Series::new(&name, values))
+			.collect();
+		Ok(DataFrame::new(columns)?)
+	}
+
+	/// Feature columns of `dataframe`, with its string columns encoded.
+	fn encode_columns(&self, dataframe: &DataFrame) -> Result<Vec<(String, Vec<f64>)>> {
+		let mut columns = Vec::new();
+		for series in dataframe.get_columns() {
+			if series.null_count() != 0 {
+				return Err(Error::invalid_input(
+					"Cannot create a dataset with null values, encountered nulls when creating the features array",
+				));
+			}
+			if series.dtype() != &DataType::Utf8 {
+				let series = series.cast::<Float64Type>()?;
+				let values = series
+					.unpack::<Float64Type>()?
+					.into_no_null_iter()
+					.collect();
+				columns.push((series.name().to_owned(), values));
+				continue;
+			}
+			let categories = self.categories(series.name()).ok_or_else(|| {
+				Error::invalid_input(format!(
+					"string column '{}' has no categories in the mapping",
+					series.name()
+				))
+			})?;
+			let values: Vec<&str> = series.utf8()?.into_no_null_iter().collect();
+			match self.encoding {
+				CategoricalEncoding::Native => {
+					let codes = values
+						.iter()
+						.map(|value| {
+							categories
+								.binary_search_by(|category| category.as_str().cmp(value))
+								.map_or(f64::NAN, |code| code as f64)
+						})
+						.collect();
+					columns.push((series.name().to_owned(), codes));
+				}
+				CategoricalEncoding::OneHot => {
+					for category in categories {
+						let indicator = values
+							.iter()
+							.map(|value| if value == category { 1. } else { 0. })
+							.collect();
+						columns.push((format!("{}={category}", series.name()), indicator));
+					}
+				}
+			}
+		}
+		Ok(columns)
+	}
+}
+
+/// Small seeded random number generator, good enough to shuffle rows
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+	pub(crate) fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+}
+
+/// Element type of a dataset field
+trait FieldType: Copy {
+	const C_API_DTYPE: i32;
+}
+
+impl FieldType for f32 {
+	const C_API_DTYPE: i32 = lightgbm_sys::C_API_DTYPE_FLOAT32;
+}
+
+impl FieldType for f64 {
+	const C_API_DTYPE: i32 = lightgbm_sys::C_API_DTYPE_FLOAT64;
+}
+
+impl FieldType for i32 {
+	const C_API_DTYPE: i32 = lightgbm_sys::C_API_DTYPE_INT32;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	fn read_train_file() -> Result<Dataset> {
+		Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+	}
+
+	#[test]
+	fn read_file() {
+		assert!(read_train_file().is_ok());
+	}
+
+	#[test]
+	fn read_file_from_path_buf() {
+		let path = std::path::PathBuf::from("lightgbm-sys/lightgbm/examples")
+			.join("binary_classification")
+			.join("binary.train");
+		let dataset = Dataset::from_file(&path).unwrap();
+		assert_eq!(dataset.n_rows(), read_train_file().unwrap().n_rows());
+		assert!(Dataset::from_files(&[path.clone(), path]).is_ok());
+	}
+
+	#[test]
+	fn read_files() {
+		let path = "lightgbm-sys/lightgbm/examples/binary_classification/binary.train";
+		let n_rows = read_train_file().unwrap().n_rows().unwrap();
+		let dataset = Dataset::from_files(&[path, path]).unwrap();
+		assert_eq!(dataset.n_rows(), Ok(2 * n_rows));
+
+		let params = Params::new()
+			.header(true)
+			.label_column(crate::LabelColumn::Name("target".to_owned()));
+		let dataset =
+			Dataset::from_files_with_params(&["test/test_header.csv"; 3], &params).unwrap();
+		assert_eq!(dataset.n_rows(), Ok(3 * 8));
+
+		assert!(Dataset::from_files(&[path, "test/test_header.csv"]).is_err());
+		assert!(Dataset::from_files::<&str>(&[]).is_err());
+	}
+
+	#[test]
+	fn read_files_with_side_files() {
+		let dir = std::env::temp_dir().join(format!("lightgbm-side-files-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let first = dir.join("first.csv");
+		let second = dir.join("second.csv");
+		fs::write(&first, "0,1.0,0.1\n1,0.7,0.4\n").unwrap();
+		fs::write(&second, "1,0.9,0.8\n").unwrap();
+		fs::write(dir.join("first.csv.weight"), "1\n2\n").unwrap();
+		assert!(Dataset::from_files(&[&first, &second]).is_err());
+
+		fs::write(dir.join("second.csv.weight"), "3\n").unwrap();
+		let dataset = Dataset::from_files(&[&first, &second]).unwrap();
+		assert_eq!(dataset.get_field::<f32>("weight"), Ok(vec![1., 2., 3.]));
+		fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn from_mat_f64_label() {
+		let label = [0.1, 2.5, -1e6 / 3.];
+		let dataset = Dataset::from_mat_f64_label(&[1., 0.7, 0.9], 3, &label).unwrap();
+		let read_label = dataset.get_label().unwrap();
+		assert_eq!(read_label.len(), 3);
+		for (read, expected) in read_label.iter().zip(label) {
+			assert!((f64::from(*read) - expected).abs() <= expected.abs() * 1e-6);
+		}
+	}
+
+	#[test]
+	fn from_csv_bytes() {
+		let csv = b"\"feature, 1\";target;feature_2\r\n1.0;0;0.1\r\n\r\n\"0.7\";1;\r\n0.9;0;na\r\n";
+		let params = Params::new()
+			.header(true)
+			.label_column(crate::LabelColumn::Name("target".to_owned()));
+		let dataset = Dataset::from_csv_bytes(csv, b';', &params).unwrap();
+		assert_eq!(dataset.n_rows().unwrap(), 3);
+		assert_eq!(dataset.n_features().unwrap(), 2);
+		assert_eq!(dataset.get_label().unwrap(), vec![0., 1., 0.]);
+		assert_eq!(
+			dataset.feature_names().unwrap(),
+			vec!["feature, 1".to_owned(), "feature_2".to_owned()]
+		);
+
+		let dataset = Dataset::from_csv_bytes(b"1,0.5,2\n0,0.1,3", b',', &Params::new()).unwrap();
+		assert_eq!(dataset.get_label().unwrap(), vec![1., 0.]);
+		assert!(Dataset::from_csv_bytes(b"1,0.5\n0", b',', &Params::new()).is_err());
+		assert!(Dataset::from_csv_bytes(b"1,\"0.5", b',', &Params::new()).is_err());
+		assert!(Dataset::from_csv_bytes(b"1,a", b',', &Params::new()).is_err());
+	}
+
+	#[test]
+	fn raw_handle() {
+		let dataset = Dataset::from_mat(&[1.0, 0.7, 0.9, 0.2], 2, &[0.0, 1.0]).unwrap();
+		let handle = dataset.into_raw_handle();
+		let dataset = unsafe { Dataset::from_raw_handle(handle) };
+		assert_eq!(dataset.handle, handle);
+		assert_eq!(dataset.shape(), Ok((2, 2)));
+		assert_eq!(dataset.get_label(), Ok(vec![0.0, 1.0]));
+	}
+
+	#[test]
+	fn read_file_with_header() {
+		let params = Params::new()
+			.header(true)
+			.label_column(crate::LabelColumn::Name("target".to_owned()));
+		let dataset = Dataset::from_file_with_params("test/test_header.csv", &params).unwrap();
+		assert_eq!(dataset.n_rows(), Ok(8));
+		assert_eq!(dataset.n_features(), Ok(3));
+		assert_eq!(
+			dataset.get_label(),
+			Ok(vec![0., 0., 0., 1., 1., 0., 1., 1.])
+		);
+		let feature_names = vec![
+			"feature_1".to_owned(),
+			"feature_2".to_owned(),
+			"feature_3".to_owned(),
+		];
+		assert_eq!(dataset.feature_names(), Ok(feature_names));
+
+		// loading the same data from memory gives the same dataset
+		let csv = fs::read("test/test_header.csv").unwrap();
+		let from_bytes = Dataset::from_csv_bytes(&csv, b',', &params).unwrap();
+		assert_eq!(from_bytes.shape(), dataset.shape());
+		assert_eq!(from_bytes.get_label(), dataset.get_label());
+		assert_eq!(from_bytes.feature_names(), dataset.feature_names());
+	}
+
+	#[test]
+	fn from_mat() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			label,
+		);
+		assert!(dataset.is_ok());
+	}
+
+	#[cfg(feature = "dataframe")]
+	#[test]
+	fn from_dataframe() {
+		use polars::df;
+		let df: DataFrame = df![
+			"feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
+			"feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7],
+			"feature_3" => [0.2, 0.5, 0.5, 0.1, 0.1],
+			"feature_4" => [0.1, 0.1, 0.1, 0.7, 0.9],
+			"label" => [0.0, 0.0, 0.0, 1.0, 1.0]
+		]
+		.unwrap();
+
+		let df_dataset = Dataset::from_dataframe(df, String::from("label"));
+		assert!(df_dataset.is_ok());
+	}
+
+	#[cfg(feature = "dataframe")]
+	#[test]
+	fn from_dataframe_column_order() {
+		use polars::df;
+		let df: DataFrame = df![
+			"feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
+			"label" => [0.0, 0.0, 0.0, 1.0, 1.0],
 			"feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7],
+			"feature_3" => [0.2, 0.5, 0.5, 0.1, 0.1]
+		]
+		.unwrap();
+		let shuffled_df: DataFrame = df![
 			"feature_3" => [0.2, 0.5, 0.5, 0.1, 0.1],
-			"feature_4" => [0.1, 0.1, 0.1, 0.7, 0.9],
-			"label" => [0.0, 0.0, 0.0, 1.0, 1.0]
+			"feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
+			"feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7]
 		]
 		.unwrap();
+		let data = [
+			[1.0, 0.1, 0.2],
+			[0.7, 0.4, 0.5],
+			[0.9, 0.8, 0.5],
+			[0.2, 0.2, 0.1],
+			[0.1, 0.7, 0.1],
+		]
+		.iter()
+		.flatten()
+		.copied()
+		.collect::<Vec<_>>();
 
-		let df_dataset = Dataset::from_dataframe(df, String::from("label"));
-		assert!(df_dataset.is_ok());
+		let dataset = Dataset::from_dataframe(df, String::from("label")).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.set("objective", "binary")
+			.set("min_data_in_leaf", 1);
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+		assert_eq!(
+			bst.feature_names(),
+			Ok(vec![
+				"feature_1".to_owned(),
+				"feature_2".to_owned(),
+				"feature_3".to_owned()
+			])
+		);
+		assert_eq!(
+			bst.predict_dataframe(&shuffled_df, crate::PredictType::Normal),
+			bst.predict_with_type(&data, 5, crate::PredictType::Normal)
+		);
+	}
+
+	#[cfg(feature = "dataframe")]
+	#[test]
+	fn from_dataframe_auto() {
+		use polars::df;
+		let df = || -> DataFrame {
+			df![
+				"color" => ["red", "blue", "red", "green", "blue", "red", "green", "blue"],
+				"size" => [1.0; 8],
+				"label" => [1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+			]
+			.unwrap()
+		};
+
+		let (dataset, mapping) =
+			Dataset::from_dataframe_auto(df(), String::from("label"), CategoricalEncoding::Native)
+				.unwrap();
+		assert_eq!(
+			mapping.categories("color"),
+			Some(&["blue".to_owned(), "green".to_owned(), "red".to_owned()][..])
+		);
+		assert_eq!(dataset.shape(), Ok((8, 2)));
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.set("objective", "binary")
+			.set("min_data_in_leaf", 1)
+			.set("min_data_per_group", 1);
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+		assert_eq!(
+			bst.feature_names(),
+			Ok(vec!["color".to_owned(), "size".to_owned()])
+		);
+		let trees = bst.export_trees().unwrap();
+		assert!(trees[0].nodes.iter().any(|node| matches!(
+			node,
+			crate::TreeNode::Split { feature: 0, categories, .. } if !categories.is_empty()
+		)));
+		// categories are sorted: blue = 0, green = 1, red = 2
+		let red = bst
+			.predict_with_type(&[2., 1.], 1, crate::PredictType::Normal)
+			.unwrap();
+		let blue = bst
+			.predict_with_type(&[0., 1.], 1, crate::PredictType::Normal)
+			.unwrap();
+		assert!(red[0] > blue[0]);
+		// prediction data with other categories gets the training codes
+		let to_predict = df![
+			"color" => ["red", "purple", "blue"],
+			"size" => [1.0; 3]
+		]
+		.unwrap();
+		let encoded = mapping.encode(&to_predict).unwrap();
+		let predictions = bst
+			.predict_dataframe(&encoded, crate::PredictType::Normal)
+			.unwrap();
+		assert_eq!(predictions[0], red[0]);
+		assert_eq!(predictions[2], blue[0]);
+
+		let (one_hot, one_hot_mapping) =
+			Dataset::from_dataframe_auto(df(), String::from("label"), CategoricalEncoding::OneHot)
+				.unwrap();
+		assert_eq!(one_hot.shape(), Ok((8, 4)));
+		assert_eq!(
+			one_hot.feature_names(),
+			Ok(vec![
+				"color=blue".to_owned(),
+				"color=green".to_owned(),
+				"color=red".to_owned(),
+				"size".to_owned()
+			])
+		);
+		let encoded = one_hot_mapping
+			.encode(&df!["color" => ["green", "purple"], "size" => [1.0; 2]].unwrap())
+			.unwrap();
+		assert_eq!(
+			encoded.get_column_names(),
+			["color=blue", "color=green", "color=red", "size"]
+		);
+		let green: Vec<f64> = encoded
+			.column("color=green")
+			.unwrap()
+			.f64()
+			.unwrap()
+			.into_no_null_iter()
+			.collect();
+		assert_eq!(green, [1., 0.]);
+	}
+
+	#[cfg(feature = "dataframe")]
+	#[test]
+	fn from_dataframe_ranking() {
+		use polars::df;
+		let df = df![
+			"feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
+			"query" => [3, 3, 8, 8, 8],
+			"weight" => [1.0, 2.0, 1.0, 1.0, 0.5],
+			"label" => [1.0, 0.0, 2.0, 1.0, 0.0]
+		]
+		.unwrap();
+		let dataset =
+			Dataset::from_dataframe_ranking(df, String::from("label"), "query", Some("weight"))
+				.unwrap();
+		assert_eq!(dataset.shape(), Ok((5, 1)));
+		assert_eq!(dataset.feature_names(), Ok(vec!["feature_1".to_owned()]));
+		// LightGBM returns the boundaries of the groups
+		assert_eq!(dataset.get_field::<i32>("group"), Ok(vec![0, 2, 5]));
+		assert_eq!(
+			dataset.get_field::<f32>("weight"),
+			Ok(vec![1.0, 2.0, 1.0, 1.0, 0.5])
+		);
+
+		let unsorted = df![
+			"feature_1" => [1.0, 0.7, 0.9],
+			"query" => [3, 8, 3],
+			"label" => [1.0, 0.0, 2.0]
+		]
+		.unwrap();
+		assert!(
+			Dataset::from_dataframe_ranking(unsorted, String::from("label"), "query", None)
+				.is_err()
+		);
 	}
 
 	#[test]
@@ -356,6 +2110,38 @@ mod tests {
 		.unwrap();
 		assert_eq!(dataset.n_rows(), Ok(5));
 		assert_eq!(dataset.n_features(), Ok(4));
+		assert_eq!(dataset.get_label(), Ok(label.to_vec()));
+	}
+
+	#[test]
+	fn from_mat_shaped() {
+		// 3 rows of 2 features
+		let data = [1.0, 0.1, 0.7, 0.4, 0.9, 0.8];
+		let dataset = Dataset::from_mat_shaped(&data, 3, 2, &[0.0, 0.0, 1.0]).unwrap();
+		assert_eq!(dataset.shape(), Ok((3, 2)));
+
+		// the transposed matrix, 2 rows of 3 values, passes shape inference
+		let transposed = [1.0, 0.7, 0.9, 0.1, 0.4, 0.8];
+		assert!(Dataset::from_mat(&transposed, 2, &[0.0, 1.0]).is_ok());
+		assert!(Dataset::from_mat_shaped(&transposed, 2, 2, &[0.0, 1.0]).is_err());
+		assert!(Dataset::from_mat_shaped(&data[1..], 3, 2, &[0.0, 0.0, 1.0]).is_err());
+	}
+
+	#[test]
+	fn from_rows_generic() {
+		let label = &[0.0, 0.0, 1.0];
+		let f32_rows: Vec<Vec<f32>> = vec![vec![1.0, 0.1], vec![0.7, 0.4], vec![0.9, 0.8]];
+		let dataset = Dataset::from_rows_generic(&f32_rows, label).unwrap();
+		assert_eq!(dataset.shape(), Ok((3, 2)));
+		assert_eq!(dataset.get_label(), Ok(label.to_vec()));
+
+		let i32_rows: Vec<Vec<i32>> = vec![vec![1, -2, 3], vec![4, 5, 6], vec![7, 8, -9]];
+		let dataset = Dataset::from_rows_generic(&i32_rows, label).unwrap();
+		assert_eq!(dataset.shape(), Ok((3, 3)));
+
+		let ragged: Vec<Vec<u8>> = vec![vec![1, 2], vec![3], vec![4, 5]];
+		assert!(Dataset::from_rows_generic(&ragged, label).is_err());
+		assert!(Dataset::from_rows_generic(&i32_rows, &label[1..]).is_err());
 	}
 
 	#[test]
@@ -378,6 +2164,30 @@ mod tests {
 		dataset.set_weights(weights).unwrap();
 	}
 
+	#[test]
+	fn set_field_by_name() {
+		let data = [[1.0, 0.1], [0.7, 0.4], [0.9, 0.8], [0.2, 0.2], [0.1, 0.7]]
+			.iter()
+			.flatten()
+			.copied()
+			.collect::<Vec<_>>();
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let weights = &[0.5, 1.0, 2.0, 0.5, 0.5];
+		let mut typed = Dataset::from_mat(&data, 5, label).unwrap();
+		typed.set_weights(weights).unwrap();
+		let mut generic = Dataset::from_mat(&data, 5, label).unwrap();
+		generic.set_field_f32("weight", weights).unwrap();
+		assert_eq!(generic.get_field::<f32>("weight"), Ok(weights.to_vec()));
+		assert_eq!(
+			generic.get_field::<f32>("weight"),
+			typed.get_field::<f32>("weight")
+		);
+
+		assert!(generic.set_field_f32("weight", &weights[1..]).is_err());
+		assert!(generic.set_field_f32("no_such_field", weights).is_err());
+		assert!(generic.set_field_i32("position", &[0, 1]).is_err());
+	}
+
 	#[test]
 	fn set_weights_wrong_len() {
 		let data = &[
@@ -399,4 +2209,488 @@ mod tests {
 		assert!(dataset.set_weights(weights_short).is_err());
 		assert!(dataset.set_weights(weights_long).is_err());
 	}
+
+	#[test]
+	fn set_class_weights() {
+		let data = &[[1.0, 0.1], [0.7, 0.4], [0.9, 0.8], [0.2, 0.2], [0.1, 0.7]];
+		let mut dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			&[0.0, 2.0, 1.0, 2.0, 0.0],
+		)
+		.unwrap();
+		dataset.set_class_weights(&[1.0, 5.0, 0.5]).unwrap();
+		assert_eq!(
+			dataset.get_field::<f32>("weight"),
+			Ok(vec![1.0, 0.5, 5.0, 0.5, 1.0])
+		);
+		assert!(dataset.set_class_weights(&[1.0, 5.0]).is_err());
+		// num_class = 4, with no record of the last class
+		dataset.set_class_weights(&[1.0, 5.0, 0.5, 2.0]).unwrap();
+		assert_eq!(
+			dataset.get_field::<f32>("weight"),
+			Ok(vec![1.0, 0.5, 5.0, 0.5, 1.0])
+		);
+
+		let mut binary = Dataset::from_mat(&[1.0, 0.7, 0.9], 3, &[0.0, 1.0, 1.5]).unwrap();
+		assert!(binary.set_class_weights(&[1.0, 2.0]).is_err());
+	}
+
+	#[test]
+	fn init_score() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let mut dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			label,
+		)
+		.unwrap();
+		assert_eq!(dataset.get_init_score(), Ok(None));
+		let init_score = vec![0.1, -0.2, 0.3, 0.0, 1.5];
+		dataset.set_init_score(&init_score).unwrap();
+		assert_eq!(dataset.get_init_score(), Ok(Some(init_score)));
+		assert!(dataset.set_init_score(&[0.1, 0.2]).is_err());
+	}
+
+	#[test]
+	fn set_fields() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let mut dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			&[0.0; 5],
+		)
+		.unwrap();
+		let label = [2.0, 1.0, 0.0, 1.0, 0.0];
+		let weight = [1.0, 0.5, 0.5, 2.0, 1.0];
+		dataset
+			.set_fields(
+				DatasetFields::new()
+					.label(&label)
+					.weight(&weight)
+					.group(&[2, 3]),
+			)
+			.unwrap();
+		assert_eq!(dataset.get_label(), Ok(label.to_vec()));
+		assert_eq!(dataset.get_field::<f32>("weight"), Ok(weight.to_vec()));
+		// LightGBM stores query boundaries rather than sizes
+		assert_eq!(dataset.get_field::<i32>("group"), Ok(vec![0, 2, 5]));
+	}
+
+	#[test]
+	fn set_fields_bad_group() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let label = [0.0, 0.0, 0.0, 1.0, 1.0];
+		let mut dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			&label,
+		)
+		.unwrap();
+		let result = dataset.set_fields(
+			DatasetFields::new()
+				.label(&[2.0, 1.0, 0.0, 1.0, 0.0])
+				.weight(&[1.0; 5])
+				.group(&[2, 2]),
+		);
+		assert!(result.is_err());
+		assert_eq!(dataset.get_label(), Ok(label.to_vec()));
+		assert_eq!(dataset.get_field::<f32>("weight"), Ok(Vec::new()));
+	}
+
+	#[test]
+	#[cfg(target_pointer_width = "64")]
+	fn mat_shape_overflow() {
+		assert_eq!(mat_shape(12, 3), Ok((3, 4)));
+		assert_eq!(mat_shape(0, 0), Ok((0, 0)));
+		assert!(mat_shape(12, 0).is_err());
+		let too_many_rows = mat_shape(1 << 32, 1 << 32).unwrap_err();
+		assert_eq!(too_many_rows.kind(), crate::ErrorKind::Conversion);
+		let too_many_columns = mat_shape(1 << 32, 1).unwrap_err();
+		assert_eq!(too_many_columns.kind(), crate::ErrorKind::Conversion);
+	}
+
+	#[test]
+	fn train_valid_split() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let data = data.iter().flatten().copied().collect::<Vec<_>>();
+		// distinct labels to tell rows apart
+		let label = &[0.0, 1.0, 2.0, 3.0, 4.0];
+		let dataset = Dataset::from_mat(&data, 5, label).unwrap();
+
+		let (train, valid) = dataset.train_valid_split(0.4, 42).unwrap();
+		assert_eq!(train.n_rows(), Ok(3));
+		assert_eq!(valid.n_rows(), Ok(2));
+		let mut labels = train.get_label().unwrap();
+		labels.extend(valid.get_label().unwrap());
+		labels.sort_by(f32::total_cmp);
+		assert_eq!(labels, label);
+
+		let (train_again, _) = dataset.train_valid_split(0.4, 42).unwrap();
+		assert_eq!(train_again.get_label(), train.get_label());
+		assert!(dataset.train_valid_split(1., 42).is_err());
+		assert!(dataset.train_valid_split(f64::NAN, 42).is_err());
+	}
+
+	#[test]
+	fn from_vec() {
+		let dataset = Dataset::from_vec(
+			vec![1.0, 0.1, 0.2, 0.7, 0.4, 0.5, 0.9, 0.8, 0.5, 0.2, 0.2, 0.8],
+			4,
+			vec![0.0, 0.0, 1.0, 1.0],
+		)
+		.unwrap();
+		assert_eq!(dataset.n_rows(), Ok(4));
+		assert_eq!(dataset.n_features(), Ok(3));
+		assert_eq!(dataset.get_label(), Ok(vec![0.0, 0.0, 1.0, 1.0]));
+		let params = Params::new()
+			.set("num_iterations", 2)
+			.set("objective", "binary");
+		assert!(crate::Booster::train_with_params(dataset, &params).is_ok());
+	}
+
+	#[test]
+	fn raw_data_is_copied() {
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.set("objective", "regression")
+			.set("min_data_in_leaf", 1)
+			.linear_tree(true);
+		let data = vec![1.0, 0.1, 0.7, 0.4, 0.9, 0.8, 0.2, 0.2, 0.1, 0.7];
+		let dataset =
+			Dataset::from_mat_with_params(&data, 5, &[0.0, 0.0, 0.0, 1.0, 1.0], &params).unwrap();
+		// LightGBM keeps the raw values for linear trees, but has its own copy
+		drop(data);
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+		let result = bst
+			.predict_with_type(&[0.5, 0.5], 1, crate::PredictType::Normal)
+			.unwrap();
+		assert!(result[0].is_finite());
+	}
+
+	#[test]
+	fn from_mat_colmajor() {
+		let rows = [
+			[1.0, 0.1, 0.2],
+			[0.7, 0.4, 0.5],
+			[0.9, 0.8, 0.5],
+			[0.2, 0.2, 0.8],
+			[0.1, 0.7, 1.0],
+			[0.3, 0.9, 0.6],
+		];
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+		let row_major = rows.iter().flatten().copied().collect::<Vec<_>>();
+		let col_major = (0..3)
+			.flat_map(|j| rows.iter().map(move |row| row[j]))
+			.collect::<Vec<_>>();
+		let params = Params::new()
+			.set("num_iterations", 3)
+			.set("objective", "binary")
+			.set("min_data_in_leaf", 1);
+
+		let predictions = [
+			Dataset::from_mat(&row_major, 6, label).unwrap(),
+			Dataset::from_mat_colmajor(&col_major, 6, 3, label).unwrap(),
+		]
+		.map(|dataset| {
+			crate::Booster::train_with_params(dataset, &params)
+				.unwrap()
+				.predict_with_type(&row_major, 6, crate::PredictType::Normal)
+				.unwrap()
+		});
+		assert_eq!(predictions[0], predictions[1]);
+
+		assert!(Dataset::from_mat_colmajor(&col_major, 6, 2, label).is_err());
+	}
+
+	#[test]
+	fn from_mat_named() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let data = data.iter().flatten().copied().collect::<Vec<_>>();
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let feature_names = ["a", "b", "c", "d"];
+		let dataset = Dataset::from_mat_named(&data, 5, label, &feature_names).unwrap();
+		let params = Params::new()
+			.set("num_iterations", 2)
+			.set("objective", "binary");
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+		assert_eq!(
+			bst.feature_names(),
+			Ok(feature_names.map(String::from).to_vec())
+		);
+		assert_eq!(bst.feature_importance().map(|i| i.len()), Ok(4));
+
+		assert!(Dataset::from_mat_named(&data, 5, label, &["a", "b"]).is_err());
+	}
+
+	#[test]
+	fn dump_text() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			label,
+		)
+		.unwrap();
+		let filename = "./test/test_dump_text.output";
+		assert_eq!(dataset.dump_text(filename), Ok(()));
+		let dump = std::fs::read_to_string(filename).unwrap();
+		let _ = std::fs::remove_file(filename);
+		assert!(dump.lines().count() >= data.len());
+		assert!(dataset.dump_text("").is_err());
+	}
+
+	#[test]
+	fn from_sampled_columns() {
+		let n_rows = 1000;
+		let data = (0..n_rows)
+			.flat_map(|i| [(i % 100) as f64 / 100., (i % 7) as f64])
+			.collect::<Vec<_>>();
+		let label = (0..n_rows)
+			.map(|i| ((i % 100) >= 50) as i32 as f32)
+			.collect::<Vec<_>>();
+
+		// sample every 10th row, omitting zeros
+		let sample = data.chunks(2).step_by(10).collect::<Vec<_>>();
+		let (sample_data, sample_indices): (Vec<Vec<f64>>, Vec<Vec<i32>>) = (0..2)
+			.map(|j| {
+				sample
+					.iter()
+					.enumerate()
+					.filter(|(_, row)| row[j] != 0.)
+					.map(|(idx, row)| (row[j], idx as i32))
+					.unzip()
+			})
+			.unzip();
+		let sample_data = sample_data.iter().map(Vec::as_slice).collect::<Vec<_>>();
+		let sample_indices = sample_indices.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+		let mut dataset = Dataset::from_sampled_columns(
+			&sample_data,
+			&sample_indices,
+			sample.len(),
+			n_rows,
+			&Params::new(),
+		)
+		.unwrap();
+		assert_eq!(dataset.n_rows(), Ok(n_rows));
+		assert_eq!(dataset.n_features(), Ok(2));
+		let (first, second) = data.split_at(600 * 2);
+		dataset.push_rows(first, 600, 0).unwrap();
+		assert!(dataset.push_rows(second, 400, 700).is_err());
+		dataset.push_rows(second, 400, 600).unwrap();
+		dataset.set_label(&label).unwrap();
+
+		let params = Params::new()
+			.set("num_iterations", 5)
+			.set("objective", "binary");
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+		let predictions = bst
+			.predict_with_type(&[0.1, 3., 0.9, 3.], 2, crate::PredictType::Normal)
+			.unwrap();
+		assert!(predictions[0] < 0.5 && predictions[1] > 0.5);
+
+		assert!(
+			Dataset::from_sampled_columns(&[&[1.]], &[&[5]], 2, n_rows, &Params::new()).is_err()
+		);
+	}
+
+	#[test]
+	fn shape() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			label,
+		)
+		.unwrap();
+		assert_eq!(dataset.shape(), Ok((5, 4)));
+	}
+
+	#[test]
+	fn bin_construct_sample_cnt() {
+		let n_rows = 1000;
+		let data = (0..n_rows).map(|i| i as f64).collect::<Vec<_>>();
+		let label = vec![0.; n_rows];
+		let num_bins = |sample_cnt| {
+			let params = Params::new().bin_construct_sample_cnt(sample_cnt);
+			Dataset::from_mat_with_params(&data, n_rows, &label, &params)
+				.unwrap()
+				.num_bins(0)
+				.unwrap()
+		};
+		let precise = num_bins(1000);
+		let coarse = num_bins(20);
+		assert!(
+			coarse < precise,
+			"{coarse} bins from 20 samples, {precise} from 1000"
+		);
+
+		let dataset = Dataset::from_mat(&data, n_rows, &label).unwrap();
+		assert!(dataset.num_bins(1).is_err());
+		assert!(Params::new()
+			.bin_construct_sample_cnt(0)
+			.validate()
+			.is_err());
+	}
+
+	#[test]
+	fn invalid_dataset_params() {
+		let data = [1.0, 0.7, 0.9, 0.2];
+		let params = Params::new().max_conflict_rate(1.);
+		assert!(Dataset::from_mat_with_params(&data, 2, &[0.0, 1.0], &params).is_err());
+		let params = Params::new().bin_construct_sample_cnt(0);
+		assert!(Dataset::from_file_with_params("test/test_header.csv", &params).is_err());
+	}
+
+	#[test]
+	fn feature_pre_filter() {
+		let n_rows = 200;
+		// the second feature is only non-zero on a few rows
+		let data = (0..n_rows)
+			.flat_map(|i| [i as f64, (i % 40 == 0) as i32 as f64])
+			.collect::<Vec<_>>();
+		let label = (0..n_rows)
+			.map(|i| (i % 40 == 0 || i > 100) as i32 as f32)
+			.collect::<Vec<_>>();
+		let params = Params::new()
+			.feature_pre_filter(false)
+			.enable_bundle(false)
+			.max_conflict_rate(0.1)
+			.min_data_in_leaf(20);
+		assert_eq!(
+			params.to_string(),
+			"enable_bundle=false feature_pre_filter=false max_conflict_rate=0.1 \
+				min_data_in_leaf=20"
+		);
+		assert!(Params::new().max_conflict_rate(1.).validate().is_err());
+
+		let dataset = Dataset::from_mat_with_params(&data, n_rows, &label, &params).unwrap();
+		let reused = Dataset::from_mat_with_reference(&data, n_rows, &label, &dataset).unwrap();
+		for (dataset, min_data_in_leaf) in [(dataset, 20), (reused, 2)] {
+			let params = params
+				.clone()
+				.min_data_in_leaf(min_data_in_leaf)
+				.set("objective", "binary")
+				.set("num_iterations", 3);
+			assert!(crate::Booster::train_with_params(dataset, &params).is_ok());
+		}
+	}
+
+	#[test]
+	fn save_binary() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let data = data.iter().flatten().copied().collect::<Vec<_>>();
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let feature_names = ["a", "b", "c", "d"];
+		let dataset = Dataset::from_mat_named(&data, 5, label, &feature_names).unwrap();
+		let filename = "./test/test_save_binary.bin";
+		assert_eq!(dataset.save_binary(filename), Ok(()));
+		let loaded = Dataset::from_binary(filename);
+		let _ = std::fs::remove_file(filename);
+		let loaded = loaded.unwrap();
+		assert_eq!(loaded.n_features(), Ok(4));
+		assert_eq!(loaded.n_rows(), Ok(5));
+		assert_eq!(
+			loaded.feature_names(),
+			Ok(feature_names.map(String::from).to_vec())
+		);
+		assert_eq!(loaded.get_label(), Ok(label.to_vec()));
+
+		assert!(Dataset::from_binary("test/test_header.csv").is_err());
+		assert!(dataset.save_binary("").is_err());
+	}
+
+	#[test]
+	fn set_feature_names() {
+		let data = &[
+			[1.0, 0.1, 0.2, 0.1],
+			[0.7, 0.4, 0.5, 0.1],
+			[0.9, 0.8, 0.5, 0.1],
+			[0.2, 0.2, 0.8, 0.7],
+			[0.1, 0.7, 1.0, 0.9],
+		];
+		let label = &[0.0, 0.0, 0.0, 1.0, 1.0];
+		let mut dataset = Dataset::from_mat(
+			&data.iter().flatten().copied().collect::<Vec<_>>(),
+			data.len(),
+			label,
+		)
+		.unwrap();
+		dataset.set_feature_names(&["a", "b", "c", "d"]).unwrap();
+		assert!(dataset.set_feature_names(&["a", "b", "c"]).is_err());
+		let err = dataset
+			.set_feature_names(&["a", "b\0", "c", "d"])
+			.unwrap_err();
+		assert!(err.to_string().contains("failed to make cstring"));
+		let err = dataset
+			.set_feature_names(&["a", "b", "a", "b"])
+			.unwrap_err();
+		assert_eq!(err.kind(), crate::ErrorKind::InvalidInput);
+		assert!(err.to_string().ends_with("duplicates: a, b"));
+	}
+
+	#[cfg(feature = "dataframe")]
+	#[test]
+	fn from_dataframe_duplicate_names() {
+		let df = DataFrame::new_no_checks(vec![
+			Series::new("feature", &[1.0, 0.7, 0.9]),
+			Series::new("feature", &[0.1, 0.4, 0.8]),
+			Series::new("other", &[0.2, 0.5, 0.5]),
+			Series::new("label", &[0.0, 0.0, 1.0]),
+		]);
+		let err = Dataset::from_dataframe(df, String::from("label")).unwrap_err();
+		assert!(err.to_string().contains("duplicates: feature"));
+	}
 }
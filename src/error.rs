@@ -11,24 +11,93 @@ use lightgbm_sys;
 #[cfg(feature = "dataframe")]
 use polars::prelude::*;
 
-/// Convenience return type for most operations which can return an `LightGBM`.
+/// Result of the fallible operations of this crate: `lightgbm::Result<T>` is
+/// `std::result::Result<T, lightgbm::Error>`.
+///
+/// Example
+/// ```
+/// use lightgbm::{Booster, ErrorKind};
+///
+/// fn num_features(model_file: &str) -> lightgbm::Result<i32> {
+/// 	let booster = Booster::from_file(model_file)?;
+/// 	booster.num_feature()
+/// }
+///
+/// fn f() -> lightgbm::Result<()> {
+/// 	assert_eq!(num_features("./test/test_from_file.input")?, 28);
+/// 	Ok(())
+/// }
+///
+/// f().unwrap();
+/// let error = num_features("./test/no_such_file").unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::Ffi);
+/// ```
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Category of an [`Error`], allowing to handle errors programmatically.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// Invalid input, e.g. data with the wrong shape or an unknown parameter
+	InvalidInput,
+	/// Error reported by the LightGBM library, or unexpected value returned by it
+	Ffi,
+	/// Failed conversion, e.g. a string containing a NUL byte, or a size that doesn't fit into
+	/// the integer type expected by LightGBM
+	Conversion,
+	/// Input/output error
+	Io,
+}
+
 /// Wrap errors returned by the LightGBM library.
+///
+/// Errors are only created by this crate. They can be inspected with
+/// [`kind`](Self::kind) and [`description`](Self::description), and display as
+/// `LightGBM error: {description}`.
 #[derive(Debug, Eq, PartialEq)]
 pub struct Error {
+	kind: ErrorKind,
 	desc: String,
 }
 
 impl Error {
-	pub(crate) fn new<S: Into<String>>(desc: S) -> Self {
-		Self { desc: desc.into() }
+	fn new<S: Into<String>>(kind: ErrorKind, desc: S) -> Self {
+		Self {
+			kind,
+			desc: desc.into(),
+		}
+	}
+
+	pub(crate) fn invalid_input<S: Into<String>>(desc: S) -> Self {
+		Self::new(ErrorKind::InvalidInput, desc)
+	}
+
+	pub(crate) fn ffi<S: Into<String>>(desc: S) -> Self {
+		Self::new(ErrorKind::Ffi, desc)
+	}
+
+	pub(crate) fn conversion<S: Into<String>>(desc: S) -> Self {
+		Self::new(ErrorKind::Conversion, desc)
+	}
+
+	pub(crate) fn io<S: Into<String>>(desc: S) -> Self {
+		Self::new(ErrorKind::Io, desc)
 	}
 
+	/// Conversion error caused by `err`
 	pub(crate) fn from_other<E: Display>(context: &str, err: E) -> Self {
-		Self {
-			desc: format!("{}: {}", context, err),
-		}
+		Self::conversion(format!("{}: {}", context, err))
+	}
+
+	/// Category of the error
+	pub fn kind(&self) -> ErrorKind {
+		self.kind
+	}
+
+	/// Description of the error, e.g. the message of the LightGBM library for
+	/// [`ErrorKind::Ffi`] errors
+	pub fn description(&self) -> &str {
+		&self.desc
 	}
 
 	/// Check the return value from an LightGBM FFI call, and return the last error message on
@@ -41,7 +110,7 @@ impl Error {
 		match ret_val {
 			0 => Ok(()),
 			-1 => Err(Self::from_lightgbm()?),
-			_ => Err(Error::new(format!(
+			_ => Err(Error::ffi(format!(
 				"unexpected return value '{}', expected 0 or -1",
 				ret_val
 			))),
@@ -54,7 +123,7 @@ impl Error {
 		let str_slice = c_str
 			.to_str()
 			.map_err(|e| Self::from_other("failed to make string", e))?;
-		Ok(Self::new(str_slice))
+		Ok(Self::ffi(str_slice))
 	}
 }
 
@@ -69,9 +138,7 @@ impl Display for Error {
 #[cfg(feature = "dataframe")]
 impl From<PolarsError> for Error {
 	fn from(pe: PolarsError) -> Self {
-		Self {
-			desc: pe.to_string(),
-		}
+		Self::conversion(pe.to_string())
 	}
 }
 
@@ -85,6 +152,15 @@ mod tests {
 		assert_eq!(result, Ok(()));
 
 		let result = Error::check_return_value(-1);
-		assert_eq!(result, Err(Error::new("Everything is fine")));
+		assert_eq!(result, Err(Error::ffi("Everything is fine")));
+		assert_eq!(result.unwrap_err().kind(), ErrorKind::Ffi);
+	}
+
+	#[test]
+	fn accessors() {
+		let error = Error::invalid_input("bad shape");
+		assert_eq!(error.kind(), ErrorKind::InvalidInput);
+		assert_eq!(error.description(), "bad shape");
+		assert_eq!(error.to_string(), "LightGBM error: bad shape");
 	}
 }
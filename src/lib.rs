@@ -8,13 +8,80 @@ macro_rules! lgbm_call {
 }
 
 mod error;
-pub use error::{Error, Result};
+pub use error::{Error, ErrorKind, Result};
+
+mod log;
+pub use log::set_log_callback;
 
 mod dataset;
-pub use dataset::Dataset;
+#[cfg(feature = "dataframe")]
+pub use dataset::{CategoricalEncoding, CategoricalMapping};
+pub use dataset::{Dataset, DatasetFields};
+
+mod params;
+pub use params::{
+	set_default_seed, BoostingType, ForcedSplit, LabelColumn, MonotoneConstraintsMethod, Objective,
+	Params, TreeLearner,
+};
 
 mod booster;
-pub use booster::Booster;
+pub use booster::{
+	blend_predictions, Booster, ClassPrediction, ContribRow, EvalResults, FeatureImportance,
+	FeatureType, ImportanceType, MissingType, PathNode, PredictType, PredictionDiff, Task, Tree,
+	TreeNode, TreePath, TreeStats,
+};
 
 mod single_row_predictor;
 pub use single_row_predictor::SingleRowPredictor;
+
+mod predict_config;
+pub use predict_config::PredictConfig;
+
+mod predictor_pool;
+pub use predictor_pool::{PooledPredictor, PredictorPool};
+
+mod train_config;
+pub use train_config::{TrainConfig, TrainStats};
+
+mod cv;
+pub use cv::suggest_num_iterations;
+
+/// Convert a path to a `CString` for the C API, from its bytes on Unix.
+///
+/// Elsewhere the path has to be valid UTF-8, which is what LightGBM expects.
+pub(crate) fn path_to_cstring(path: &std::path::Path) -> Result<std::ffi::CString> {
+	#[cfg(unix)]
+	let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+	#[cfg(not(unix))]
+	let bytes = path
+		.to_str()
+		.ok_or_else(|| Error::conversion(format!("path {} is not valid UTF-8", path.display())))?
+		.as_bytes();
+	std::ffi::CString::new(bytes).map_err(|e| Error::from_other("failed to make cstring", e))
+}
+
+/// Whether the LightGBM library this crate links was built with OpenMP.
+///
+/// Without OpenMP, LightGBM runs single-threaded and the `num_threads` parameter has no
+/// effect. LightGBM is built with OpenMP when the `openmp` feature of this crate is enabled.
+pub fn has_openmp() -> bool {
+	cfg!(feature = "openmp")
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn path_to_cstring() {
+		let path = std::path::PathBuf::from("test").join("model.txt");
+		assert_eq!(
+			super::path_to_cstring(&path).unwrap().to_str(),
+			Ok(path.to_str().unwrap())
+		);
+		assert!(super::path_to_cstring("a\0b".as_ref()).is_err());
+	}
+
+	#[test]
+	fn has_openmp() {
+		let _: bool = super::has_openmp();
+	}
+}
@@ -0,0 +1,48 @@
+//! Routing of the log messages of LightGBM, and of the warnings of this crate.
+
+use {
+	libc::c_char,
+	std::{ffi::CStr, sync::RwLock},
+};
+
+use crate::{Error, Result};
+
+type LogCallback = Box<dyn Fn(&str) + Send + Sync>;
+
+static LOG_CALLBACK: RwLock<Option<LogCallback>> = RwLock::new(None);
+
+/// Send the log messages of LightGBM to `callback` instead of printing them to stdout.
+///
+/// Messages are passed as LightGBM formats them, e.g. `[LightGBM] [Warning] ...`, and may
+/// be split into several calls. Warnings of this crate about questionable parameters are
+/// sent to the same callback. LightGBM logs from whatever thread it runs on, so the callback
+/// must be thread-safe.
+///
+/// Example
+/// ```
+/// lightgbm::set_log_callback(|message| eprint!("{message}")).unwrap();
+/// ```
+pub fn set_log_callback<F>(callback: F) -> Result<()>
+where
+	F: Fn(&str) + Send + Sync + 'static,
+{
+	*LOG_CALLBACK.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(callback));
+	lgbm_call!(lightgbm_sys::LGBM_RegisterLogCallback(Some(log_trampoline)))
+}
+
+unsafe extern "C" fn log_trampoline(message: *const c_char) {
+	let message = CStr::from_ptr(message).to_string_lossy();
+	log(&message);
+}
+
+fn log(message: &str) {
+	match &*LOG_CALLBACK.read().unwrap_or_else(|e| e.into_inner()) {
+		Some(callback) => callback(message),
+		None => print!("{message}"),
+	}
+}
+
+/// Emit a warning formatted like those of LightGBM.
+pub(crate) fn warn(message: &str) {
+	log(&format!("[LightGBM] [Warning] {message}\n"));
+}
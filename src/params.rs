@@ -0,0 +1,1586 @@
+//! Parameters passed to LightGBM for dataset construction and training.
+
+use {
+	libc::c_char,
+	serde_json::Value,
+	std::{
+		collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+		ffi::CString,
+		fmt::{self, Display},
+		fs,
+		hash::{Hash, Hasher},
+		sync::{OnceLock, RwLock},
+	},
+};
+
+use crate::{Error, Result};
+
+/// Parameters for LightGBM, serialized as space-separated `key=value` pairs.
+///
+/// Example
+/// ```
+/// use lightgbm::Params;
+///
+/// let params = Params::new()
+/// 	.set("objective", "binary")
+/// 	.set("metric", "auc");
+/// assert_eq!(params.to_string(), "metric=auc objective=binary");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+	params: BTreeMap<String, String>,
+	/// Invalid values given to typed setters, reported by `validate`
+	invalid: Vec<String>,
+}
+
+impl Params {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set an arbitrary parameter by name.
+	///
+	/// This is the escape hatch for parameters that don't have a typed setter.
+	pub fn set<K: Into<String>, V: ToString>(mut self, key: K, value: V) -> Self {
+		self.params.insert(key.into(), value.to_string());
+		self
+	}
+
+	/// Objective function to optimize, along with the parameters it requires.
+	pub fn objective(mut self, objective: Objective) -> Self {
+		match objective {
+			Objective::Huber { alpha } => {
+				if alpha.is_nan() || alpha <= 0. {
+					self.invalid
+						.push(format!("huber alpha should be positive, got {alpha}"));
+				}
+				self = self.set("alpha", alpha);
+			}
+			Objective::Fair { c } => {
+				if c.is_nan() || c <= 0. {
+					self.invalid
+						.push(format!("fair c should be positive, got {c}"));
+				}
+				self = self.set("fair_c", c);
+			}
+			Objective::Quantile { alpha } => {
+				if !(alpha > 0. && alpha < 1.) {
+					self.invalid
+						.push(format!("quantile alpha should be in (0, 1), got {alpha}"));
+				}
+				self = self.set("alpha", alpha);
+			}
+			Objective::Tweedie { variance_power } => {
+				if !(1. ..2.).contains(&variance_power) {
+					self.invalid.push(format!(
+						"tweedie variance_power should be in [1, 2), got {variance_power}"
+					));
+				}
+				self = self.set("tweedie_variance_power", variance_power);
+			}
+			Objective::MulticlassSoftmax { num_class } | Objective::MulticlassOva { num_class } => {
+				if num_class < 2 {
+					self.invalid.push(format!(
+						"multiclass objectives need num_class of at least 2, got {num_class}"
+					));
+				}
+				self = self.set("num_class", num_class);
+			}
+			_ => {}
+		}
+		self.set("objective", objective.name())
+	}
+
+	/// Tree learning algorithm, see [`TreeLearner`].
+	pub fn tree_learner(self, tree_learner: TreeLearner) -> Self {
+		self.set("tree_learner", tree_learner.name())
+	}
+
+	/// Boosting algorithm, see [`BoostingType`].
+	pub fn boosting(self, boosting: BoostingType) -> Self {
+		self.set("boosting", boosting.name())
+	}
+
+	/// Fraction of the trees to drop at each iteration with [`BoostingType::Dart`], default
+	/// is 0.1.
+	pub fn drop_rate(self, drop_rate: f64) -> Self {
+		self.fraction("drop_rate", drop_rate)
+	}
+
+	/// Maximum number of trees to drop at each iteration with [`BoostingType::Dart`], `<= 0`
+	/// means no limit. Default is 50.
+	pub fn max_drop(self, max_drop: i32) -> Self {
+		self.set("max_drop", max_drop)
+	}
+
+	/// Probability of not dropping any tree at an iteration with [`BoostingType::Dart`],
+	/// default is 0.5.
+	pub fn skip_drop(self, skip_drop: f64) -> Self {
+		self.fraction("skip_drop", skip_drop)
+	}
+
+	/// Fraction of the data with the largest gradients that is kept with
+	/// [`BoostingType::Goss`], default is 0.2.
+	///
+	/// `top_rate + other_rate` must not exceed 1.
+	pub fn top_rate(mut self, top_rate: f64) -> Self {
+		self = self.fraction("top_rate", top_rate);
+		self.check_goss_rates();
+		self
+	}
+
+	/// Fraction of the data with small gradients that is sampled with
+	/// [`BoostingType::Goss`], default is 0.1.
+	///
+	/// `top_rate + other_rate` must not exceed 1.
+	pub fn other_rate(mut self, other_rate: f64) -> Self {
+		self = self.fraction("other_rate", other_rate);
+		self.check_goss_rates();
+		self
+	}
+
+	/// Set a parameter that must be in `[0, 1]`.
+	fn fraction(mut self, name: &str, value: f64) -> Self {
+		if !(0.0..=1.0).contains(&value) {
+			self.invalid
+				.push(format!("{name} should be in [0, 1], got {value}"));
+		}
+		self.set(name, value)
+	}
+
+	fn check_goss_rates(&mut self) {
+		let rate = |name| self.get(name).and_then(|v| v.parse::<f64>().ok());
+		if let (Some(top_rate), Some(other_rate)) = (rate("top_rate"), rate("other_rate")) {
+			if top_rate + other_rate > 1. {
+				self.invalid.push(format!(
+					"top_rate ({top_rate}) + other_rate ({other_rate}) should not exceed 1"
+				));
+			}
+		}
+	}
+
+	/// Whether the input data file has a header line (dataset parameter).
+	pub fn header(self, header: bool) -> Self {
+		self.set("header", header)
+	}
+
+	/// Which column of the input data file holds the label (dataset parameter).
+	///
+	/// This applies to datasets loaded from data, such as
+	/// [`Dataset::from_file_with_params`](crate::Dataset::from_file_with_params) and
+	/// [`Dataset::from_csv_bytes`](crate::Dataset::from_csv_bytes), and defaults to the first
+	/// column. Whichever column it is, its values are stored in the `label` field of the
+	/// dataset, as returned by [`Dataset::get_label`](crate::Dataset::get_label), and the
+	/// column isn't a feature. Matrix constructors such as
+	/// [`Dataset::from_mat_with_params`](crate::Dataset::from_mat_with_params) take the labels
+	/// separately and ignore this parameter.
+	pub fn label_column(self, label_column: LabelColumn) -> Self {
+		self.set("label_column", label_column)
+	}
+
+	/// Force column-wise histogram building.
+	///
+	/// Column-wise building reduces memory usage and is usually faster when the dataset has many
+	/// columns or many total bins, or when `num_threads` is large.
+	///
+	/// By default LightGBM benchmarks both at the start of training and picks the fastest.
+	/// Cannot be combined with [`force_row_wise`](Self::force_row_wise).
+	pub fn force_col_wise(self, force_col_wise: bool) -> Self {
+		debug_assert!(
+			!(force_col_wise && self.get("force_row_wise") == Some("true")),
+			"force_col_wise and force_row_wise cannot both be enabled"
+		);
+		self.set("force_col_wise", force_col_wise)
+	}
+
+	/// Force row-wise histogram building.
+	///
+	/// Row-wise building is usually faster when the dataset has many rows and relatively few
+	/// total bins, or when `num_threads` is small, at the cost of using more memory.
+	///
+	/// By default LightGBM benchmarks both at the start of training and picks the fastest.
+	/// Cannot be combined with [`force_col_wise`](Self::force_col_wise).
+	pub fn force_row_wise(self, force_row_wise: bool) -> Self {
+		debug_assert!(
+			!(force_row_wise && self.get("force_col_wise") == Some("true")),
+			"force_col_wise and force_row_wise cannot both be enabled"
+		);
+		self.set("force_row_wise", force_row_wise)
+	}
+
+	/// Use piecewise linear models in the leaves instead of constants.
+	///
+	/// This has to be set both when constructing the dataset (e.g. with
+	/// [`Dataset::from_mat_with_params`](crate::Dataset::from_mat_with_params)) and when
+	/// training. Increases memory usage and training time.
+	pub fn linear_tree(self, linear_tree: bool) -> Self {
+		self.set("linear_tree", linear_tree)
+	}
+
+	/// Number of records sampled to decide the bin boundaries of the features (dataset
+	/// parameter). Must be at least 1, default is 200000.
+	///
+	/// Larger values give more precise bins, at the cost of memory and dataset construction
+	/// time. This has to be set when constructing the dataset, e.g. with
+	/// [`Dataset::from_mat_with_params`](crate::Dataset::from_mat_with_params).
+	pub fn bin_construct_sample_cnt(mut self, bin_construct_sample_cnt: u32) -> Self {
+		if bin_construct_sample_cnt < 1 {
+			self.invalid
+				.push("bin_construct_sample_cnt should be at least 1, got 0".to_owned());
+		}
+		self.set("bin_construct_sample_cnt", bin_construct_sample_cnt)
+	}
+
+	/// Whether to bundle mutually exclusive features, i.e. sparse features that are rarely
+	/// non-zero for the same record (dataset parameter). Default is `true`.
+	///
+	/// Bundling speeds up training on sparse high-dimensional data, disabling it may help
+	/// when the data is dense.
+	pub fn enable_bundle(self, enable_bundle: bool) -> Self {
+		self.set("enable_bundle", enable_bundle)
+	}
+
+	/// Fraction of records for which features that are bundled together may both be non-zero
+	/// (dataset parameter). Must be in `[0, 1)`, default is 0.
+	///
+	/// Larger values allow more bundling, so faster training, at the cost of accuracy.
+	pub fn max_conflict_rate(mut self, max_conflict_rate: f64) -> Self {
+		if !(0.0..1.0).contains(&max_conflict_rate) {
+			self.invalid.push(format!(
+				"max_conflict_rate should be in [0, 1), got {max_conflict_rate}"
+			));
+		}
+		self.set("max_conflict_rate", max_conflict_rate)
+	}
+
+	/// Whether to filter out, when constructing the dataset, features that can't be split on
+	/// because of [`min_data_in_leaf`](Self::min_data_in_leaf) (dataset parameter). Default
+	/// is `true`.
+	///
+	/// Set it to `false` to train with different values of `min_data_in_leaf` on datasets
+	/// sharing the same binning (e.g. created with
+	/// [`Dataset::from_mat_with_reference`](crate::Dataset::from_mat_with_reference)), as
+	/// filtered features could otherwise never be used.
+	pub fn feature_pre_filter(self, feature_pre_filter: bool) -> Self {
+		self.set("feature_pre_filter", feature_pre_filter)
+	}
+
+	/// Quantize gradients and hessians to low-bit integers during training, which speeds up
+	/// training at a small cost in accuracy.
+	pub fn use_quantized_grad(self, use_quantized_grad: bool) -> Self {
+		self.set("use_quantized_grad", use_quantized_grad)
+	}
+
+	/// Number of bins used to quantize gradients and hessians when
+	/// [`use_quantized_grad`](Self::use_quantized_grad) is enabled. Must be at least 2.
+	///
+	/// With more bins, quantized training gets closer to full precision training.
+	pub fn num_grad_quant_bins(mut self, num_grad_quant_bins: u32) -> Self {
+		if num_grad_quant_bins < 2 {
+			self.invalid.push(format!(
+				"num_grad_quant_bins should be at least 2, got {num_grad_quant_bins}"
+			));
+		}
+		self.set("num_grad_quant_bins", num_grad_quant_bins)
+	}
+
+	/// Weight of the positive class in binary classification, to compensate for imbalanced
+	/// labels. Typically the number of negative records divided by the number of positive
+	/// ones. Must be positive, default is 1.
+	///
+	/// This is mutually exclusive with [`is_unbalance`](Self::is_unbalance): setting both logs
+	/// a warning (see [`set_log_callback`](crate::set_log_callback)).
+	pub fn scale_pos_weight(mut self, scale_pos_weight: f64) -> Self {
+		if scale_pos_weight.is_nan() || scale_pos_weight <= 0. {
+			self.invalid.push(format!(
+				"scale_pos_weight should be positive, got {scale_pos_weight}"
+			));
+		}
+		self = self.set("scale_pos_weight", scale_pos_weight);
+		self.warn_if_unbalance_and_scale_pos_weight();
+		self
+	}
+
+	/// Weigh the classes of a binary classification so that they have the same total
+	/// weight, i.e. compute [`scale_pos_weight`](Self::scale_pos_weight) from the labels.
+	///
+	/// This is mutually exclusive with `scale_pos_weight`: setting both logs a warning.
+	pub fn is_unbalance(mut self, is_unbalance: bool) -> Self {
+		self = self.set("is_unbalance", is_unbalance);
+		self.warn_if_unbalance_and_scale_pos_weight();
+		self
+	}
+
+	/// Whether to start boosting from the average label, adjusted to the objective (e.g. the
+	/// log-odds of the positive rate for binary classification), rather than from 0. Default
+	/// is `true`, and it only applies to the regression, binary and cross-entropy objectives.
+	///
+	/// The average is folded into the leaves of the first tree, so it is part of the model's
+	/// raw predictions. Disable it to boost from 0, e.g. when labels are centered already.
+	/// LightGBM always skips it when the training data has an
+	/// [init score](crate::Dataset::set_init_score), which then is the starting point.
+	pub fn boost_from_average(self, boost_from_average: bool) -> Self {
+		self.set("boost_from_average", boost_from_average)
+	}
+
+	fn warn_if_unbalance_and_scale_pos_weight(&self) {
+		if self.get("is_unbalance") == Some("true") && self.get("scale_pos_weight").is_some() {
+			crate::log::warn(
+				"is_unbalance and scale_pos_weight are mutually exclusive, \
+					LightGBM will fail to train with both",
+			);
+		}
+	}
+
+	/// Maximum number of leaves in one tree. Must be at least 2, default is 31.
+	///
+	/// This is the main parameter controlling the complexity of the trees, as LightGBM grows
+	/// trees leaf-wise. A tree of depth `d` has at most `2^d` leaves, so setting this above
+	/// `2^max_depth` has no effect beyond [`max_depth`](Self::max_depth) and usually means
+	/// the two were confused: this logs a warning (see
+	/// [`set_log_callback`](crate::set_log_callback)).
+	pub fn num_leaves(mut self, num_leaves: u32) -> Self {
+		if num_leaves < 2 {
+			self.invalid
+				.push(format!("num_leaves should be at least 2, got {num_leaves}"));
+		}
+		self = self.set("num_leaves", num_leaves);
+		self.warn_if_num_leaves_exceeds_max_depth();
+		self
+	}
+
+	/// Maximum depth of the trees. `-1` means unlimited, which is the default.
+	///
+	/// Limiting the depth helps against overfitting on small datasets, as trees are grown
+	/// leaf-wise. See [`num_leaves`](Self::num_leaves) for how the two interact.
+	pub fn max_depth(mut self, max_depth: i32) -> Self {
+		self = self.set("max_depth", max_depth);
+		self.warn_if_num_leaves_exceeds_max_depth();
+		self
+	}
+
+	fn warn_if_num_leaves_exceeds_max_depth(&self) {
+		let num_leaves = self.get("num_leaves").and_then(|v| v.parse::<u64>().ok());
+		let max_depth = self.get("max_depth").and_then(|v| v.parse::<u32>().ok());
+		if let (Some(num_leaves), Some(max_depth @ 1..=63)) = (num_leaves, max_depth) {
+			if num_leaves > 1 << max_depth {
+				crate::log::warn(&format!(
+					"num_leaves ({num_leaves}) is larger than 2^max_depth ({}), \
+						trees can't have more than 2^max_depth leaves",
+					1_u64 << max_depth
+				));
+			}
+		}
+	}
+
+	/// Maximum size of the cache of histograms, in MB. `-1` means unlimited, which is the
+	/// default.
+	///
+	/// Capping it trades speed for memory: histograms that don't fit in the cache have to be
+	/// recomputed. This is useful on memory-limited hosts.
+	pub fn histogram_pool_size(mut self, histogram_pool_size: f64) -> Self {
+		if histogram_pool_size.is_nan() || (histogram_pool_size <= 0. && histogram_pool_size != -1.)
+		{
+			self.invalid.push(format!(
+				"histogram_pool_size should be positive or -1, got {histogram_pool_size}"
+			));
+		}
+		self.set("histogram_pool_size", histogram_pool_size)
+	}
+
+	/// Seed from which LightGBM derives the seeds it uses for sampling (e.g.
+	/// `data_random_seed`, `bagging_seed`), which take precedence if set.
+	///
+	/// If this isn't set, the seed set with [`set_default_seed`](crate::set_default_seed) is
+	/// used, if any.
+	pub fn seed(self, seed: u64) -> Self {
+		self.set("seed", seed)
+	}
+
+	/// Minimal number of data points in one leaf. Must be at least 1.
+	///
+	/// This is one of the most important parameters to prevent overfitting: larger values stop
+	/// the trees from growing leaves that only describe a handful of samples, at the risk of
+	/// underfitting. Default is 20.
+	pub fn min_data_in_leaf(mut self, min_data_in_leaf: u32) -> Self {
+		if min_data_in_leaf < 1 {
+			self.invalid
+				.push("min_data_in_leaf should be at least 1, got 0".to_owned());
+		}
+		self.set("min_data_in_leaf", min_data_in_leaf)
+	}
+
+	/// Minimal sum of hessians in one leaf. Must be non-negative.
+	///
+	/// Like [`min_data_in_leaf`](Self::min_data_in_leaf), this prevents overfitting, but weighs
+	/// samples by their hessian. Default is `1e-3`.
+	pub fn min_sum_hessian_in_leaf(mut self, min_sum_hessian_in_leaf: f64) -> Self {
+		if min_sum_hessian_in_leaf.is_nan() || min_sum_hessian_in_leaf < 0. {
+			self.invalid.push(format!(
+				"min_sum_hessian_in_leaf should be non-negative, got {min_sum_hessian_in_leaf}"
+			));
+		}
+		self.set("min_sum_hessian_in_leaf", min_sum_hessian_in_leaf)
+	}
+
+	/// Minimal gain required to perform a split. Must be non-negative.
+	///
+	/// Any split that doesn't improve the objective by at least this much is not made, which
+	/// prunes splits that barely help. Default is 0.
+	pub fn min_gain_to_split(mut self, min_gain_to_split: f64) -> Self {
+		if min_gain_to_split.is_nan() || min_gain_to_split < 0. {
+			self.invalid.push(format!(
+				"min_gain_to_split should be non-negative, got {min_gain_to_split}"
+			));
+		}
+		self.set("min_gain_to_split", min_gain_to_split)
+	}
+
+	/// Monotone constraint of each feature: `1` for increasing, `-1` for decreasing, `0` for
+	/// none. There must be one constraint per feature.
+	pub fn monotone_constraints(mut self, monotone_constraints: &[i8]) -> Self {
+		if let Some(constraint) = monotone_constraints
+			.iter()
+			.find(|constraint| !(-1..=1).contains(*constraint))
+		{
+			self.invalid.push(format!(
+				"monotone constraints should be -1, 0 or 1, got {constraint}"
+			));
+		}
+		let monotone_constraints = monotone_constraints
+			.iter()
+			.map(ToString::to_string)
+			.collect::<Vec<_>>();
+		self.set("monotone_constraints", monotone_constraints.join(","))
+	}
+
+	/// Method enforcing the [monotone constraints](Self::monotone_constraints), see
+	/// [`MonotoneConstraintsMethod`].
+	pub fn monotone_constraints_method(self, method: MonotoneConstraintsMethod) -> Self {
+		self.set("monotone_constraints_method", method.name())
+	}
+
+	/// Penalty on splits on monotone features near the root of the trees. Must be
+	/// non-negative, default is 0.
+	///
+	/// Monotone constraints restrict the leaves more the higher up a constrained split is, so
+	/// penalizing these splits keeps room for the rest of the tree: splits at depth `d` have
+	/// their gain multiplied by `1 - penalty / 2^d` (down to 0). Higher values trade the
+	/// accuracy of the first splits for better constrained trees.
+	pub fn monotone_penalty(mut self, monotone_penalty: f64) -> Self {
+		if monotone_penalty.is_nan() || monotone_penalty < 0. {
+			self.invalid.push(format!(
+				"monotone_penalty should be non-negative, got {monotone_penalty}"
+			));
+		}
+		self.set("monotone_penalty", monotone_penalty)
+	}
+
+	/// Maximal number of categories of a categorical feature for its splits to be one-vs-rest.
+	/// Must be at least 1.
+	///
+	/// Features with at most this many categories are split by separating one category from
+	/// the others. Features with more categories use many-vs-many splits instead, which sort
+	/// the categories by their gradient statistics and split them in two sets: these are more
+	/// expressive but overfit more easily, which the other `cat_` parameters regularize.
+	/// Default is 4.
+	pub fn max_cat_to_onehot(mut self, max_cat_to_onehot: u32) -> Self {
+		if max_cat_to_onehot < 1 {
+			self.invalid
+				.push("max_cat_to_onehot should be at least 1, got 0".to_owned());
+		}
+		self.set("max_cat_to_onehot", max_cat_to_onehot)
+	}
+
+	/// Maximal number of categories on one side of a many-vs-many categorical split. Must be
+	/// at least 1.
+	///
+	/// Lower values make categorical splits simpler and faster to find. Default is 32.
+	pub fn max_cat_threshold(mut self, max_cat_threshold: u32) -> Self {
+		if max_cat_threshold < 1 {
+			self.invalid
+				.push("max_cat_threshold should be at least 1, got 0".to_owned());
+		}
+		self.set("max_cat_threshold", max_cat_threshold)
+	}
+
+	/// L2 regularization of many-vs-many categorical splits. Must be non-negative, default is
+	/// 10.
+	pub fn cat_l2(mut self, cat_l2: f64) -> Self {
+		if cat_l2.is_nan() || cat_l2 < 0. {
+			self.invalid
+				.push(format!("cat_l2 should be non-negative, got {cat_l2}"));
+		}
+		self.set("cat_l2", cat_l2)
+	}
+
+	/// Smoothing of the gradient statistics by which categories are sorted in many-vs-many
+	/// categorical splits. Must be non-negative.
+	///
+	/// This reduces the effect of noise in rare categories. Default is 10.
+	pub fn cat_smooth(mut self, cat_smooth: f64) -> Self {
+		if cat_smooth.is_nan() || cat_smooth < 0. {
+			self.invalid.push(format!(
+				"cat_smooth should be non-negative, got {cat_smooth}"
+			));
+		}
+		self.set("cat_smooth", cat_smooth)
+	}
+
+	/// Minimal number of data points per categorical group of a split. Must be at least 1.
+	///
+	/// Like [`min_data_in_leaf`](Self::min_data_in_leaf) for categorical splits, so high values
+	/// prevent splitting on rare categories. Default is 100.
+	pub fn min_data_per_group(mut self, min_data_per_group: u32) -> Self {
+		if min_data_per_group < 1 {
+			self.invalid
+				.push("min_data_per_group should be at least 1, got 0".to_owned());
+		}
+		self.set("min_data_per_group", min_data_per_group)
+	}
+
+	/// JSON file describing splits to force at the top of every tree.
+	///
+	/// The file contains a tree of splits, each being an object with the zero-based `feature`
+	/// index, the `threshold` to split at, and optionally the `left` and `right` splits to
+	/// force below it:
+	/// ```json
+	/// {"feature": 0, "threshold": 1.5, "left": {"feature": 2, "threshold": 0.5}}
+	/// ```
+	/// The path can't contain whitespace, as LightGBM parameters are whitespace-separated.
+	/// See also [`forced_splits`](Self::forced_splits), which writes that file for you.
+	pub fn forced_splits_file(mut self, path: &str) -> Self {
+		if path.contains(char::is_whitespace) {
+			self.invalid.push(format!(
+				"forced splits file path can't contain whitespace, got '{path}'"
+			));
+		}
+		self.set("forcedsplits_filename", path)
+	}
+
+	/// Splits to force at the top of every tree.
+	///
+	/// LightGBM reads forced splits from a file, so this writes `spec` as JSON into the system
+	/// temporary directory and sets [`forced_splits_file`](Self::forced_splits_file) to it.
+	/// The file is named after its content so setting the same splits again reuses it, and it
+	/// is not removed afterwards.
+	pub fn forced_splits(mut self, spec: &ForcedSplit) -> Self {
+		if let Err(invalid) = spec.check() {
+			self.invalid.push(invalid);
+			return self;
+		}
+		let json = spec.to_json().to_string();
+		let mut hasher = DefaultHasher::new();
+		json.hash(&mut hasher);
+		let path = std::env::temp_dir().join(format!(
+			"lightgbm-forced-splits-{:016x}.json",
+			hasher.finish()
+		));
+		if let Err(e) = fs::write(&path, json) {
+			self.invalid.push(format!(
+				"failed to write forced splits to {}: {e}",
+				path.display()
+			));
+			return self;
+		}
+		match path.to_str() {
+			Some(path) => self.forced_splits_file(path),
+			None => {
+				self.invalid.push(format!(
+					"temporary directory path is not valid UTF-8: {}",
+					path.display()
+				));
+				self
+			}
+		}
+	}
+
+	/// Get the serialized value of a parameter, if it has been set.
+	pub fn get(&self, key: &str) -> Option<&str> {
+		self.params.get(key).map(String::as_str)
+	}
+
+	/// Check that every parameter name is known to LightGBM.
+	///
+	/// LightGBM only warns about unknown parameters and then ignores them, so a typo would
+	/// otherwise go unnoticed until after training. Names are checked against the parameters
+	/// and aliases reported by the linked LightGBM library.
+	///
+	/// Parameters that LightGBM would ignore because of other parameters are also reported,
+	/// all at once: `num_class` without a multiclass objective, `eval_at` without a ranking
+	/// metric, and `scale_pos_weight` or `is_unbalance` without the binary objective.
+	pub fn validate(&self) -> Result<()> {
+		if let Some(invalid) = self.invalid.first() {
+			return Err(Error::invalid_input(invalid.clone()));
+		}
+		let known_parameters = known_parameters()?;
+		for key in self.params.keys() {
+			if key == "free_raw_data" {
+				return Err(Error::invalid_input(
+					"free_raw_data is a Python package parameter, LightGBM always copies the data \
+						so there is nothing to free",
+				));
+			}
+			if !known_parameters.contains_key(key) {
+				return Err(match closest_parameter(key, known_parameters) {
+					Some(suggestion) => Error::invalid_input(format!(
+						"unknown parameter '{key}', did you mean '{suggestion}'?"
+					)),
+					None => Error::invalid_input(format!("unknown parameter '{key}'")),
+				});
+			}
+		}
+		let conflicts = self.conflicts(known_parameters);
+		if !conflicts.is_empty() {
+			return Err(Error::invalid_input(format!(
+				"contradictory parameters: {}",
+				conflicts.join("; ")
+			)));
+		}
+		Ok(())
+	}
+
+	/// Find parameters that LightGBM would silently ignore because of other parameters, e.g.
+	/// `num_class` with a non-multiclass objective.
+	fn conflicts(&self, known_parameters: &HashMap<String, String>) -> Vec<String> {
+		let get = |name: &str| self.get_canonical(name, known_parameters);
+		let objective = get("objective").unwrap_or("regression");
+		let is_multiclass_ova = matches!(
+			objective,
+			"multiclassova" | "multiclass_ova" | "ova" | "ovr"
+		);
+		let is_multiclass = is_multiclass_ova || matches!(objective, "multiclass" | "softmax");
+		let is_ranking = matches!(
+			objective,
+			"lambdarank" | "rank_xendcg" | "xendcg" | "xe_ndcg" | "xe_ndcg_mart" | "xendcg_mart"
+		);
+		let has_ranking_metric = match get("metric") {
+			Some(metric) => metric.split(',').any(|metric| {
+				matches!(
+					metric.trim(),
+					"ndcg"
+						| "lambdarank" | "rank_xendcg"
+						| "xendcg" | "xe_ndcg"
+						| "xe_ndcg_mart" | "xendcg_mart"
+						| "map" | "mean_average_precision"
+				)
+			}),
+			None => is_ranking,
+		};
+
+		let mut conflicts = Vec::new();
+		if let Some(num_class) = get("num_class") {
+			if num_class != "1" && !is_multiclass {
+				conflicts.push(format!(
+					"num_class ({num_class}) is only used by multiclass objectives, \
+						but objective is {objective}"
+				));
+			}
+		}
+		if get("eval_at").is_some() && !has_ranking_metric {
+			conflicts.push("eval_at is only used by the ndcg and map metrics".to_owned());
+		}
+		// one-vs-all multiclass objectives train a binary objective per class, which uses them
+		let is_binary = objective == "binary" || is_multiclass_ova;
+		let is_unbalance = get("is_unbalance").is_some_and(|value| value != "false");
+		let scale_pos_weight =
+			get("scale_pos_weight").is_some_and(|value| value.parse::<f64>() != Ok(1.));
+		for (binary_only, is_set) in [
+			("scale_pos_weight", scale_pos_weight),
+			("is_unbalance", is_unbalance),
+		] {
+			if is_set && !is_binary {
+				conflicts.push(format!(
+					"{binary_only} is only used by the binary objective, but objective is {objective}"
+				));
+			}
+		}
+		conflicts
+	}
+
+	/// Value of the parameter whose canonical name is `name`, set under that name or one of
+	/// its aliases (e.g. `num_trees` for `num_iterations`).
+	fn get_canonical(
+		&self,
+		name: &str,
+		known_parameters: &HashMap<String, String>,
+	) -> Option<&str> {
+		self.get(name).or_else(|| {
+			self.params
+				.iter()
+				.find(|(key, _)| known_parameters.get(*key).map(String::as_str) == Some(name))
+				.map(|(_, value)| value.as_str())
+		})
+	}
+
+	/// Number of boosting iterations to train, 100 if not set.
+	pub(crate) fn num_iterations(&self) -> Result<i64> {
+		match self.get_canonical("num_iterations", known_parameters()?) {
+			None => Ok(100),
+			Some(num_iterations) => num_iterations
+				.parse()
+				.map_err(|_| Error::invalid_input("failed to unwrap num_iterations")),
+		}
+	}
+
+	/// Number of classes, if the objective is a multiclass one.
+	pub(crate) fn multiclass_num_class(&self) -> Result<Option<u32>> {
+		match self.get("objective") {
+			Some("multiclass" | "softmax" | "multiclassova" | "multiclass_ova" | "ova" | "ovr") => {
+				let num_class = self.get("num_class").ok_or_else(|| {
+					Error::invalid_input("multiclass objectives need num_class to be set")
+				})?;
+				num_class.parse().map(Some).map_err(|_| {
+					Error::invalid_input(format!(
+						"num_class should be a positive integer, got {num_class}"
+					))
+				})
+			}
+			_ => Ok(None),
+		}
+	}
+
+	pub(crate) fn to_cstring(&self) -> Result<CString> {
+		let default_seed = *DEFAULT_SEED.read().unwrap_or_else(|e| e.into_inner());
+		CString::new(self.to_string_with_default_seed(default_seed))
+			.map_err(|e| Error::from_other("failed to make cstring", e))
+	}
+
+	/// Serialize the parameters, adding `seed=<default_seed>` unless a seed is set.
+	fn to_string_with_default_seed(&self, default_seed: Option<u64>) -> String {
+		let has_seed = ["seed", "random_seed", "random_state"]
+			.iter()
+			.any(|&alias| self.params.contains_key(alias));
+		match default_seed {
+			Some(seed) if !has_seed => self.clone().seed(seed).to_string(),
+			_ => self.to_string(),
+		}
+	}
+}
+
+static DEFAULT_SEED: RwLock<Option<u64>> = RwLock::new(None);
+
+/// Set the seed used by every [`Params`] that doesn't set one with [`Params::seed`], making the
+/// whole process deterministic.
+///
+/// A seed set explicitly on the parameters, including through the `random_seed` or
+/// `random_state` aliases, takes precedence. Like [`Params::seed`], this doesn't override the
+/// more specific seeds such as `bagging_seed` when they are set.
+///
+/// Example
+/// ```
+/// use lightgbm::{Booster, Dataset, Params};
+///
+/// lightgbm::set_default_seed(42);
+/// let params = Params::new()
+/// 	.set("objective", "binary")
+/// 	.set("bagging_fraction", 0.5)
+/// 	.set("bagging_freq", 1);
+/// let train = || {
+/// 	let dataset = Dataset::from_file(
+/// 		"lightgbm-sys/lightgbm/examples/binary_classification/binary.train",
+/// 	)
+/// 	.unwrap();
+/// 	Booster::train_with_params(dataset, &params).unwrap()
+/// };
+/// assert_eq!(train().save_string().unwrap(), train().save_string().unwrap());
+/// ```
+pub fn set_default_seed(seed: u64) {
+	*DEFAULT_SEED.write().unwrap_or_else(|e| e.into_inner()) = Some(seed);
+}
+
+impl Display for Params {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, (k, v)) in self.params.iter().enumerate() {
+			if i != 0 {
+				f.write_str(" ")?;
+			}
+			write!(f, "{k}={v}")?;
+		}
+		Ok(())
+	}
+}
+
+/// Objective function, see [`Params::objective`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Objective {
+	/// L2 regression
+	Regression,
+	/// L1 regression
+	RegressionL1,
+	/// Huber loss regression, `alpha` being the threshold between the quadratic and linear
+	/// parts of the loss. Must be positive.
+	Huber { alpha: f64 },
+	/// Fair loss regression. `c` must be positive.
+	Fair { c: f64 },
+	/// Quantile regression, `alpha` being the quantile to predict. Must be in `(0, 1)`.
+	Quantile { alpha: f64 },
+	/// Tweedie regression, predictions are on the log scale. `variance_power` must be in
+	/// `[1, 2)`: closer to 2 shifts towards a Gamma distribution, closer to 1 towards a
+	/// Poisson distribution.
+	Tweedie { variance_power: f64 },
+	/// Poisson regression, predictions are on the log scale
+	Poisson,
+	/// Gamma regression, predictions are on the log scale
+	Gamma,
+	/// Binary classification with log loss, labels should be 0 or 1
+	Binary,
+	/// Cross-entropy, labels should be probabilities in `[0, 1]`
+	CrossEntropy,
+	/// Multiclass classification with softmax, labels should be in `0..num_class`
+	MulticlassSoftmax { num_class: u32 },
+	/// Multiclass classification with one binary classifier per class (one-vs-all), labels
+	/// should be in `0..num_class`
+	MulticlassOva { num_class: u32 },
+	/// LambdaRank ranking, requires groups to be set on the dataset
+	LambdaRank,
+	/// XE_NDCG_MART ranking, requires groups to be set on the dataset
+	RankXendcg,
+}
+
+impl Objective {
+	fn name(&self) -> &'static str {
+		match self {
+			Objective::Regression => "regression",
+			Objective::RegressionL1 => "regression_l1",
+			Objective::Huber { .. } => "huber",
+			Objective::Fair { .. } => "fair",
+			Objective::Quantile { .. } => "quantile",
+			Objective::Tweedie { .. } => "tweedie",
+			Objective::Poisson => "poisson",
+			Objective::Gamma => "gamma",
+			Objective::Binary => "binary",
+			Objective::CrossEntropy => "cross_entropy",
+			Objective::MulticlassSoftmax { .. } => "multiclass",
+			Objective::MulticlassOva { .. } => "multiclassova",
+			Objective::LambdaRank => "lambdarank",
+			Objective::RankXendcg => "rank_xendcg",
+		}
+	}
+}
+
+/// Split forced at the top of every tree, see [`Params::forced_splits`].
+///
+/// Example
+/// ```
+/// use lightgbm::ForcedSplit;
+///
+/// // split on feature 0 first, then on feature 2 for the data points that went left
+/// let spec = ForcedSplit::new(0, 1.5).left(ForcedSplit::new(2, 0.5));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForcedSplit {
+	/// Zero-based index of the feature to split on
+	pub feature: usize,
+	/// Data points with a feature value `<= threshold` go left
+	pub threshold: f64,
+	/// Split forced on the left child, if any
+	pub left: Option<Box<ForcedSplit>>,
+	/// Split forced on the right child, if any
+	pub right: Option<Box<ForcedSplit>>,
+}
+
+impl ForcedSplit {
+	pub fn new(feature: usize, threshold: f64) -> Self {
+		Self {
+			feature,
+			threshold,
+			left: None,
+			right: None,
+		}
+	}
+
+	/// Force `split` on the left child.
+	pub fn left(mut self, split: ForcedSplit) -> Self {
+		self.left = Some(Box::new(split));
+		self
+	}
+
+	/// Force `split` on the right child.
+	pub fn right(mut self, split: ForcedSplit) -> Self {
+		self.right = Some(Box::new(split));
+		self
+	}
+
+	fn check(&self) -> std::result::Result<(), String> {
+		if !self.threshold.is_finite() {
+			return Err(format!(
+				"forced split threshold should be finite, got {}",
+				self.threshold
+			));
+		}
+		self.left
+			.iter()
+			.chain(&self.right)
+			.try_for_each(|split| split.check())
+	}
+
+	fn to_json(&self) -> Value {
+		let mut json = serde_json::json!({
+			"feature": self.feature,
+			"threshold": self.threshold,
+		});
+		if let Some(left) = &self.left {
+			json["left"] = left.to_json();
+		}
+		if let Some(right) = &self.right {
+			json["right"] = right.to_json();
+		}
+		json
+	}
+}
+
+/// Tree learning algorithm, see [`Params::tree_learner`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeLearner {
+	/// Single machine tree learner (default)
+	Serial,
+	/// Feature parallel tree learner, which can help on wide data even on a single machine
+	Feature,
+	/// Data parallel tree learner, typically requires the network setup for distributed
+	/// training
+	Data,
+	/// Voting parallel tree learner, typically requires the network setup for distributed
+	/// training
+	Voting,
+}
+
+impl TreeLearner {
+	fn name(self) -> &'static str {
+		match self {
+			TreeLearner::Serial => "serial",
+			TreeLearner::Feature => "feature",
+			TreeLearner::Data => "data",
+			TreeLearner::Voting => "voting",
+		}
+	}
+}
+
+/// Method enforcing monotone constraints, see [`Params::monotone_constraints_method`].
+///
+/// The more advanced methods constrain the leaves less, usually giving more accurate
+/// models, at the cost of training time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonotoneConstraintsMethod {
+	/// Fastest method, constraining the children of a split by the mean of their outputs
+	/// (default)
+	Basic,
+	/// Constrains leaves only by the leaves they are compared to, slightly slower
+	Intermediate,
+	/// Constrains leaves by thresholds rather than by the whole leaves, slowest
+	Advanced,
+}
+
+impl MonotoneConstraintsMethod {
+	fn name(self) -> &'static str {
+		match self {
+			MonotoneConstraintsMethod::Basic => "basic",
+			MonotoneConstraintsMethod::Intermediate => "intermediate",
+			MonotoneConstraintsMethod::Advanced => "advanced",
+		}
+	}
+}
+
+/// Boosting algorithm, see [`Params::boosting`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoostingType {
+	/// Gradient boosted decision trees (default)
+	Gbdt,
+	/// Dropouts meet multiple additive regression trees, which drops trees at each
+	/// iteration to prevent over-specialization, see [`Params::drop_rate`]
+	Dart,
+	/// Gradient-based one-side sampling, which trains each tree on the data with the
+	/// largest gradients and a sample of the rest, see [`Params::top_rate`]. Newer LightGBM
+	/// versions prefer `data_sample_strategy=goss` with `Gbdt`.
+	Goss,
+	/// Random forest, which requires bagging (`bagging_freq` and `bagging_fraction`)
+	Rf,
+}
+
+impl BoostingType {
+	fn name(self) -> &'static str {
+		match self {
+			BoostingType::Gbdt => "gbdt",
+			BoostingType::Dart => "dart",
+			BoostingType::Goss => "goss",
+			BoostingType::Rf => "rf",
+		}
+	}
+}
+
+/// Column of a data file used as the label, see [`Params::label_column`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LabelColumn {
+	/// Zero-based column index.
+	Index(usize),
+	/// Column name, looked up in the file header (requires [`Params::header`]).
+	Name(String),
+}
+
+impl Display for LabelColumn {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			LabelColumn::Index(index) => write!(f, "{index}"),
+			LabelColumn::Name(name) => write!(f, "name:{name}"),
+		}
+	}
+}
+
+/// exchange params {"x": "y", "z": 1, "w": ["a", "b"]} => "x=y z=1 w=a,b"
+impl TryFrom<&Value> for Params {
+	type Error = Error;
+
+	fn try_from(value: &Value) -> Result<Self> {
+		let params = value
+			.as_object()
+			.ok_or_else(|| Error::invalid_input("failed to convert param to object"))?
+			.iter()
+			.map(|(k, v)| (k.clone(), json_value_to_param(v)))
+			.collect();
+		Ok(Self {
+			params,
+			invalid: Vec::new(),
+		})
+	}
+}
+
+fn json_value_to_param(value: &Value) -> String {
+	match value {
+		Value::String(s) => s.clone(),
+		Value::Array(values) => values
+			.iter()
+			.map(json_value_to_param)
+			.collect::<Vec<_>>()
+			.join(","),
+		other => other.to_string(),
+	}
+}
+
+/// All parameter names and aliases known to LightGBM, mapped to their canonical name, queried
+/// once from the library.
+fn known_parameters() -> Result<&'static HashMap<String, String>> {
+	static KNOWN_PARAMETERS: OnceLock<HashMap<String, String>> = OnceLock::new();
+	if let Some(known_parameters) = KNOWN_PARAMETERS.get() {
+		return Ok(known_parameters);
+	}
+
+	// get nessesary buffer size
+	let mut out_size = 0_i64;
+	lgbm_call!(lightgbm_sys::LGBM_DumpParamAliases(
+		0,
+		&mut out_size as *mut _,
+		std::ptr::null_mut()
+	))?;
+
+	// write data to buffer and convert
+	let mut buffer = vec![
+		0u8;
+		out_size.try_into().map_err(|_| Error::ffi(
+			"string size returned by C API is negative"
+		))?
+	];
+	lgbm_call!(lightgbm_sys::LGBM_DumpParamAliases(
+		out_size,
+		&mut out_size as *mut _,
+		buffer.as_mut_ptr() as *mut c_char
+	))?;
+
+	if buffer.pop() != Some(0) {
+		// this should never happen, unless lightgbm has a bug
+		panic!("write out of bounds happened in lightgbm call");
+	}
+
+	let aliases: BTreeMap<String, Vec<String>> = serde_json::from_slice(&buffer)
+		.map_err(|e| Error::from_other("failed to parse parameter aliases", e))?;
+	let known_parameters = aliases
+		.into_iter()
+		.flat_map(|(name, aliases)| {
+			std::iter::once(name.clone())
+				.chain(aliases)
+				.map(move |alias| (alias, name.clone()))
+		})
+		.collect();
+	Ok(KNOWN_PARAMETERS.get_or_init(|| known_parameters))
+}
+
+/// Find the parameter whose name or alias is closest to `key`, if any is close enough to be a
+/// likely typo.
+fn closest_parameter<'a>(
+	key: &str,
+	known_parameters: &'a HashMap<String, String>,
+) -> Option<&'a str> {
+	let max_distance = (key.len() / 3).max(2);
+	known_parameters
+		.iter()
+		.map(|(alias, name)| (edit_distance(key, alias), name))
+		.filter(|&(distance, _)| distance <= max_distance)
+		.min()
+		.map(|(_, name)| name.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+	let b = b.chars().collect::<Vec<_>>();
+	let mut row = (0..=b.len()).collect::<Vec<_>>();
+	for (i, ca) in a.chars().enumerate() {
+		let mut diagonal = row[0];
+		row[0] = i + 1;
+		for (j, &cb) in b.iter().enumerate() {
+			let substitution = diagonal + usize::from(ca != cb);
+			diagonal = row[j + 1];
+			row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+		}
+	}
+	row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, serde_json::json};
+
+	#[test]
+	fn from_json() {
+		let params = Params::try_from(&json! {
+			{
+				"num_iterations": 3,
+				"objective": "binary",
+				"metric": ["auc", "binary_logloss"]
+			}
+		})
+		.unwrap();
+		assert_eq!(
+			params.to_string(),
+			"metric=auc,binary_logloss num_iterations=3 objective=binary"
+		);
+	}
+
+	#[test]
+	fn label_column() {
+		let params = Params::new()
+			.header(true)
+			.label_column(LabelColumn::Name("target".to_owned()));
+		assert_eq!(params.to_string(), "header=true label_column=name:target");
+		let params = Params::new().label_column(LabelColumn::Index(2));
+		assert_eq!(params.to_string(), "label_column=2");
+	}
+
+	#[test]
+	#[should_panic(expected = "cannot both be enabled")]
+	#[cfg(debug_assertions)]
+	fn force_col_wise_and_row_wise() {
+		let _ = Params::new().force_col_wise(true).force_row_wise(true);
+	}
+
+	#[test]
+	fn forced_splits() {
+		let spec = ForcedSplit::new(0, 1.5).right(ForcedSplit::new(2, -0.5));
+		let params = Params::new().forced_splits(&spec);
+		assert_eq!(params.validate(), Ok(()));
+		let path = params.get("forcedsplits_filename").unwrap();
+		let written: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+		assert_eq!(
+			written,
+			json!({
+				"feature": 0,
+				"threshold": 1.5,
+				"right": {"feature": 2, "threshold": -0.5},
+			})
+		);
+
+		let params = Params::new().forced_splits(&ForcedSplit::new(0, f64::NAN));
+		assert!(params.validate().is_err());
+		let params = Params::new().forced_splits_file("forced splits.json");
+		assert!(params.validate().is_err());
+	}
+
+	#[test]
+	fn unbalanced_binary() {
+		let params = Params::new().scale_pos_weight(4.5);
+		assert_eq!(params.to_string(), "scale_pos_weight=4.5");
+		assert!(Params::new().scale_pos_weight(0.).validate().is_err());
+
+		let warning = "is_unbalance and scale_pos_weight";
+		let _ = Params::new().is_unbalance(true);
+		assert!(!_logged(warning));
+		let _ = params.is_unbalance(true);
+		assert!(_logged(warning));
+	}
+
+	#[test]
+	fn num_leaves_and_max_depth() {
+		let params = Params::new().num_leaves(63).max_depth(6);
+		assert_eq!(params.to_string(), "max_depth=6 num_leaves=63");
+		assert_eq!(params.validate(), Ok(()));
+		assert!(!_logged("num_leaves (63)"));
+		let _ = Params::new().num_leaves(31).max_depth(-1);
+		assert!(!_logged("num_leaves (31)"));
+		assert!(Params::new().num_leaves(1).validate().is_err());
+
+		let _ = Params::new().max_depth(3).num_leaves(255);
+		assert!(_logged("num_leaves (255) is larger than 2^max_depth (8)"));
+	}
+
+	/// Whether a message containing `pattern` was logged since the first call.
+	///
+	/// The log callback is global, so tests checking for warnings share this one.
+	fn _logged(pattern: &str) -> bool {
+		static MESSAGES: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+		static REGISTER: std::sync::Once = std::sync::Once::new();
+		REGISTER.call_once(|| {
+			crate::set_log_callback(|message| {
+				MESSAGES.lock().unwrap().push(message.to_owned());
+			})
+			.unwrap();
+		});
+		let messages = MESSAGES.lock().unwrap();
+		messages.iter().any(|message| message.contains(pattern))
+	}
+
+	#[test]
+	fn histogram_pool_size() {
+		let params = Params::new().histogram_pool_size(0.5);
+		assert_eq!(params.to_string(), "histogram_pool_size=0.5");
+		assert_eq!(Params::new().histogram_pool_size(-1.).validate(), Ok(()));
+		assert!(Params::new().histogram_pool_size(0.).validate().is_err());
+
+		let dataset = crate::Dataset::from_file(
+			"lightgbm-sys/lightgbm/examples/binary_classification/binary.train",
+		)
+		.unwrap();
+		let params = params
+			.objective(Objective::Binary)
+			.set("num_iterations", 3)
+			.set("data_random_seed", 0);
+		assert!(crate::Booster::train_with_params(dataset, &params).is_ok());
+	}
+
+	#[test]
+	fn boost_from_average() {
+		let params = Params::new().boost_from_average(false);
+		assert_eq!(params.to_string(), "boost_from_average=false");
+
+		let n_rows = 200;
+		let data: Vec<f64> = (0..n_rows)
+			.flat_map(|i| [(i % 17) as f64, (i * 7 % 13) as f64])
+			.collect();
+		let label: Vec<f32> = (0..n_rows).map(|i| (i % 17 > 4) as u8 as f32).collect();
+		let mut dataset = crate::Dataset::from_mat(&data, n_rows, &label).unwrap();
+		// base score of 0.7 as a margin
+		let base_margin = (0.7_f64 / 0.3).ln();
+		dataset.set_init_score(&vec![base_margin; n_rows]).unwrap();
+		let params = params
+			.objective(Objective::Binary)
+			.min_data_in_leaf(5)
+			.set("num_iterations", 20);
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+
+		let raw = bst
+			.predict_with_type(&data, n_rows, crate::PredictType::RawScore)
+			.unwrap();
+		assert!(raw.iter().all(|r| r.is_finite()));
+		let correct = raw
+			.iter()
+			.zip(&label)
+			.filter(|&(&raw, &label)| (raw + base_margin > 0.) == (label > 0.5))
+			.count();
+		assert!(correct > n_rows * 9 / 10);
+		assert!(crate::Booster::from_string(&bst.save_string().unwrap()).is_ok());
+
+		// without an init score, the first tree starts from the log-odds of the positive rate
+		let first_tree_raw = |boost_from_average: bool| {
+			let dataset = crate::Dataset::from_mat(&data, n_rows, &label).unwrap();
+			let params = Params::new()
+				.objective(Objective::Binary)
+				.min_data_in_leaf(5)
+				.boost_from_average(boost_from_average)
+				.set("num_iterations", 1);
+			let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+			let raw = bst
+				.predict_with_type(&data, n_rows, crate::PredictType::RawScore)
+				.unwrap();
+			raw.iter().sum::<f64>() / n_rows as f64
+		};
+		let positive_rate = label.iter().map(|&l| f64::from(l)).sum::<f64>() / n_rows as f64;
+		let log_odds = (positive_rate / (1. - positive_rate)).ln();
+		let shift = first_tree_raw(true) - first_tree_raw(false);
+		assert!((shift - log_odds).abs() < 0.25, "{shift} vs {log_odds}");
+	}
+
+	#[test]
+	fn categorical_params() {
+		let params = Params::new()
+			.max_cat_to_onehot(8)
+			.max_cat_threshold(16)
+			.cat_l2(5.)
+			.cat_smooth(20.)
+			.min_data_per_group(50);
+		assert!(params.to_string().contains("max_cat_to_onehot=8"));
+		assert_eq!(
+			params.to_string(),
+			"cat_l2=5 cat_smooth=20 max_cat_threshold=16 max_cat_to_onehot=8 \
+				min_data_per_group=50"
+		);
+		assert_eq!(params.validate(), Ok(()));
+		assert!(Params::new().max_cat_to_onehot(0).validate().is_err());
+		assert!(Params::new().cat_smooth(-1.).validate().is_err());
+	}
+
+	#[test]
+	fn monotone_constraints() {
+		let mut constraints = vec![0; 28];
+		constraints[0] = 1;
+		constraints[1] = -1;
+		let params = Params::new()
+			.monotone_constraints(&constraints[..3])
+			.monotone_constraints_method(MonotoneConstraintsMethod::Advanced)
+			.monotone_penalty(1.5);
+		assert_eq!(
+			params.to_string(),
+			"monotone_constraints=1,-1,0 monotone_constraints_method=advanced monotone_penalty=1.5"
+		);
+		assert_eq!(params.validate(), Ok(()));
+		assert!(Params::new().monotone_penalty(-1.).validate().is_err());
+		assert!(Params::new()
+			.monotone_constraints(&[2, 0])
+			.validate()
+			.is_err());
+
+		let dataset = crate::Dataset::from_file(
+			"lightgbm-sys/lightgbm/examples/binary_classification/binary.train",
+		)
+		.unwrap();
+		let params = params
+			.monotone_constraints(&constraints)
+			.objective(Objective::Binary)
+			.set("num_iterations", 5)
+			.set("data_random_seed", 0);
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+		let mut row = [0.5; 28];
+		let mut previous = f64::NEG_INFINITY;
+		for value in [0., 0.5, 1., 2., 5.] {
+			row[0] = value;
+			let prediction = bst
+				.predict_with_type(&row, 1, crate::PredictType::Normal)
+				.unwrap()[0];
+			assert!((0.0..=1.0).contains(&prediction));
+			assert!(prediction >= previous);
+			previous = prediction;
+		}
+	}
+
+	#[test]
+	fn default_seed() {
+		let params = Params::new().set("objective", "binary");
+		assert_eq!(params.to_string_with_default_seed(None), "objective=binary");
+		assert_eq!(
+			params.to_string_with_default_seed(Some(7)),
+			"objective=binary seed=7"
+		);
+		let seeded = params.clone().seed(3);
+		assert_eq!(
+			seeded.to_string_with_default_seed(Some(7)),
+			"objective=binary seed=3"
+		);
+		let aliased = params.set("random_state", 3);
+		assert_eq!(
+			aliased.to_string_with_default_seed(Some(7)),
+			"objective=binary random_state=3"
+		);
+	}
+
+	#[test]
+	fn boosting() {
+		let params = Params::new()
+			.boosting(BoostingType::Dart)
+			.drop_rate(0.2)
+			.max_drop(10)
+			.skip_drop(0.3);
+		assert_eq!(
+			params.to_string(),
+			"boosting=dart drop_rate=0.2 max_drop=10 skip_drop=0.3"
+		);
+		assert_eq!(params.validate(), Ok(()));
+		assert!(Params::new().drop_rate(1.5).validate().is_err());
+		assert!(Params::new().skip_drop(-0.1).validate().is_err());
+		let goss = Params::new()
+			.boosting(BoostingType::Goss)
+			.top_rate(0.3)
+			.other_rate(0.2);
+		assert_eq!(goss.validate(), Ok(()));
+		assert!(goss.other_rate(0.8).validate().is_err());
+
+		let dataset = crate::Dataset::from_file(
+			"lightgbm-sys/lightgbm/examples/binary_classification/binary.train",
+		)
+		.unwrap();
+		let params = params
+			.objective(Objective::Binary)
+			.set("num_iterations", 5)
+			.set("data_random_seed", 0);
+		let bst = crate::Booster::train_with_params(dataset, &params).unwrap();
+		let prediction = bst
+			.predict_with_type(&[0.5; 28], 1, crate::PredictType::Normal)
+			.unwrap();
+		assert!((0.0..=1.0).contains(&prediction[0]));
+		assert_eq!(
+			bst.training_params()
+				.unwrap()
+				.get("boosting")
+				.map(String::as_str),
+			Some("dart")
+		);
+	}
+
+	#[test]
+	fn validate_contradictory() {
+		let params = Params::new()
+			.set("objective", "regression")
+			.set("num_class", 3);
+		let error = params.validate().unwrap_err().to_string();
+		assert!(error.contains("num_class (3) is only used by multiclass objectives"));
+
+		let params = Params::new()
+			.set("objective", "binary")
+			.set("scale_pos_weight", 2);
+		assert_eq!(params.validate(), Ok(()));
+		let error = params
+			.set("objective", "regression")
+			.set("eval_at", "1,3")
+			.validate()
+			.unwrap_err()
+			.to_string();
+		assert!(error.contains("eval_at is only used by the ndcg and map metrics"));
+		assert!(error.contains("scale_pos_weight is only used by the binary objective"));
+
+		let ranking = Params::new()
+			.set("application", "lambdarank")
+			.set("eval_at", "1,3");
+		assert_eq!(ranking.validate(), Ok(()));
+
+		// default values don't conflict
+		let params = Params::new()
+			.set("objective", "regression")
+			.is_unbalance(false)
+			.set("scale_pos_weight", 1);
+		assert_eq!(params.validate(), Ok(()));
+		// one-vs-all objectives use them
+		let params = Params::new()
+			.set("objective", "multiclassova")
+			.set("num_class", 3)
+			.is_unbalance(true);
+		assert_eq!(params.validate(), Ok(()));
+		let params = Params::new()
+			.set("objective", "ova")
+			.set("num_class", 3)
+			.set("scale_pos_weight", 2);
+		assert_eq!(params.validate(), Ok(()));
+		assert!(Params::new()
+			.set("objective", "multiclass")
+			.set("num_class", 3)
+			.is_unbalance(true)
+			.validate()
+			.is_err());
+	}
+
+	#[test]
+	fn min_leaf_constraints() {
+		let params = Params::new()
+			.min_data_in_leaf(50)
+			.min_sum_hessian_in_leaf(0.5)
+			.min_gain_to_split(0.1);
+		assert_eq!(
+			params.to_string(),
+			"min_data_in_leaf=50 min_gain_to_split=0.1 min_sum_hessian_in_leaf=0.5"
+		);
+		assert_eq!(params.validate(), Ok(()));
+		assert!(Params::new().min_data_in_leaf(0).validate().is_err());
+		assert!(Params::new().min_gain_to_split(-1.).validate().is_err());
+		assert!(Params::new()
+			.min_sum_hessian_in_leaf(f64::NAN)
+			.validate()
+			.is_err());
+	}
+
+	#[test]
+	fn objective() {
+		let params = Params::new().objective(Objective::Binary);
+		assert_eq!(params.to_string(), "objective=binary");
+		assert_eq!(params.multiclass_num_class(), Ok(None));
+
+		let params = Params::new().objective(Objective::MulticlassSoftmax { num_class: 3 });
+		assert_eq!(params.to_string(), "num_class=3 objective=multiclass");
+		assert_eq!(params.multiclass_num_class(), Ok(Some(3)));
+
+		let params = Params::new().objective(Objective::Tweedie {
+			variance_power: 1.5,
+		});
+		assert_eq!(
+			params.to_string(),
+			"objective=tweedie tweedie_variance_power=1.5"
+		);
+		assert_eq!(params.validate(), Ok(()));
+		assert!(Params::new()
+			.objective(Objective::Tweedie { variance_power: 2. })
+			.validate()
+			.is_err());
+
+		let params = Params::new().objective(Objective::Quantile { alpha: 0.9 });
+		assert_eq!(params.to_string(), "alpha=0.9 objective=quantile");
+		assert!(Params::new()
+			.objective(Objective::Quantile { alpha: 1. })
+			.validate()
+			.is_err());
+		assert!(Params::new()
+			.objective(Objective::Huber { alpha: 0. })
+			.validate()
+			.is_err());
+		assert_eq!(
+			Params::new()
+				.objective(Objective::Fair { c: 2. })
+				.to_string(),
+			"fair_c=2 objective=fair"
+		);
+
+		let params = Params::new().objective(Objective::MulticlassOva { num_class: 1 });
+		assert_eq!(
+			params.validate(),
+			Err(Error::invalid_input(
+				"multiclass objectives need num_class of at least 2, got 1"
+			))
+		);
+	}
+
+	#[test]
+	fn validate_known_parameters() {
+		let params = Params::new()
+			.set("objective", "binary")
+			.set("num_leaves", 7)
+			.set("num_trees", 3); // alias of num_iterations
+		assert_eq!(params.validate(), Ok(()));
+	}
+
+	#[test]
+	fn validate_invalid_value() {
+		let params = Params::new()
+			.use_quantized_grad(true)
+			.num_grad_quant_bins(1);
+		assert_eq!(
+			params.validate(),
+			Err(Error::invalid_input(
+				"num_grad_quant_bins should be at least 2, got 1"
+			))
+		);
+	}
+
+	#[test]
+	fn validate_unknown_parameter() {
+		let params = Params::new().set("objective", "binary").set("num_leafs", 7);
+		assert_eq!(
+			params.validate(),
+			Err(Error::invalid_input(
+				"unknown parameter 'num_leafs', did you mean 'num_leaves'?"
+			))
+		);
+		let params = Params::new().set("free_raw_data", false);
+		assert!(params.validate().is_err());
+	}
+}
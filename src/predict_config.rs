@@ -0,0 +1,66 @@
+//! Prediction parameters, for what [`Booster::predict_with_type`](crate::Booster::predict_with_type)
+//! doesn't support.
+
+use crate::{Error, Params, PredictType, Result};
+
+/// Configuration of a prediction, see [`Booster::predict_with_config`](crate::Booster::predict_with_config).
+///
+/// Example
+/// ```
+/// use lightgbm::{Booster, PredictConfig, PredictType};
+///
+/// let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+/// let config = PredictConfig::new(PredictType::Normal).early_stopping(10, 2.);
+/// let prediction = bst.predict_with_config(&[0.5; 28], 1, &config).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PredictConfig {
+	pub(crate) predict_type: PredictType,
+	pub(crate) params: Params,
+}
+
+impl PredictConfig {
+	/// Predict results of type `predict_type`.
+	pub fn new(predict_type: PredictType) -> Self {
+		Self {
+			predict_type,
+			params: Params::new(),
+		}
+	}
+
+	/// Stop evaluating the trees for a row once its prediction is confident enough, checking
+	/// every `freq` trees whether the margin of the raw score exceeds `margin`.
+	///
+	/// This trades accuracy for latency: the prediction is that of the trees evaluated so far,
+	/// so it differs from the full model's, although the predicted class rarely changes when
+	/// `margin` is large enough. The margin is twice the absolute raw score for binary
+	/// classification, and the difference between the two highest raw scores for multiclass
+	/// classification. LightGBM ignores this for other objectives.
+	pub fn early_stopping(mut self, freq: u32, margin: f64) -> Self {
+		self.params = self
+			.params
+			.set("pred_early_stop", true)
+			.set("pred_early_stop_freq", freq)
+			.set("pred_early_stop_margin", margin);
+		self
+	}
+
+	/// Check the configuration, and serialize it as prediction parameters on top of
+	/// `param_overrides`.
+	pub(crate) fn parameters(&self, param_overrides: &str) -> Result<String> {
+		if self.params.get("pred_early_stop_freq") == Some("0") {
+			return Err(Error::invalid_input(
+				"early stopping frequency should be at least 1",
+			));
+		}
+		match self.params.get("pred_early_stop_margin") {
+			Some(margin) if !margin.parse::<f64>().is_ok_and(|margin| margin >= 0.) => {
+				return Err(Error::invalid_input(format!(
+					"early stopping margin should be non-negative, got {margin}"
+				)));
+			}
+			_ => {}
+		}
+		Ok(format!("{param_overrides} {}", self.params))
+	}
+}
@@ -0,0 +1,213 @@
+//! Pool of reusable prediction resources, for serving predictions from many threads.
+
+use std::{
+	ops::Deref,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Mutex,
+	},
+};
+
+use crate::{Booster, Error, PredictType, Result, SingleRowPredictor};
+
+/// Pool of output buffers and [`SingleRowPredictor`]s for a [`Booster`], so that
+/// predicting from many threads doesn't allocate once the pool is warm.
+///
+/// Each thread takes a [`PooledPredictor`] with [`get`](Self::get), predicts into its buffer,
+/// and returns it to the pool by dropping it. The pool holds as many buffers as there were
+/// predictors in use at the same time.
+///
+/// Example
+/// ```
+/// use lightgbm::{Booster, PredictType, PredictorPool};
+///
+/// let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+/// let pool = PredictorPool::new(&bst);
+/// std::thread::scope(|scope| {
+/// 	for _ in 0..4 {
+/// 		scope.spawn(|| {
+/// 			let mut predictor = pool.get();
+/// 			let prediction = predictor.predict_single_row(&[0.5; 28]).unwrap();
+/// 			assert_eq!(prediction.len(), 1);
+/// 		});
+/// 	}
+/// });
+/// ```
+pub struct PredictorPool<'a> {
+	booster: &'a Booster,
+	entries: Mutex<Vec<PoolEntry<'a>>>,
+	num_entries: AtomicUsize,
+}
+
+struct PoolEntry<'a> {
+	buffer: Vec<f64>,
+	single_row_predictor: Option<SingleRowPredictor<'a>>,
+}
+
+impl<'a> PredictorPool<'a> {
+	/// Create an empty pool predicting with `booster`.
+	pub fn new(booster: &'a Booster) -> Self {
+		Self {
+			booster,
+			entries: Mutex::new(Vec::new()),
+			num_entries: AtomicUsize::new(0),
+		}
+	}
+
+	/// The booster predictions are made with
+	pub fn booster(&self) -> &'a Booster {
+		self.booster
+	}
+
+	/// Take a predictor from the pool, or create one if they are all in use.
+	pub fn get(&self) -> PooledPredictor<'_, 'a> {
+		let entry = self.entries.lock().unwrap().pop().unwrap_or_else(|| {
+			self.num_entries.fetch_add(1, Ordering::Relaxed);
+			PoolEntry {
+				buffer: Vec::new(),
+				single_row_predictor: None,
+			}
+		});
+		PooledPredictor {
+			pool: self,
+			entry: Some(entry),
+		}
+	}
+
+	/// Number of predictors created by the pool, i.e. the highest number of predictors that
+	/// were in use at the same time.
+	pub fn num_predictors(&self) -> usize {
+		self.num_entries.load(Ordering::Relaxed)
+	}
+}
+
+/// Predictor taken from a [`PredictorPool`], returned to the pool on drop.
+///
+/// Predictions are written into a buffer owned by the predictor, so they are only borrowed
+/// until the next prediction.
+pub struct PooledPredictor<'p, 'a> {
+	pool: &'p PredictorPool<'a>,
+	entry: Option<PoolEntry<'a>>,
+}
+
+impl PooledPredictor<'_, '_> {
+	/// Predict results of the given type for `n_rows` rows of data in row-major order, like
+	/// [`Booster::predict_with_type`].
+	pub fn predict(
+		&mut self,
+		data: &[f64],
+		n_rows: usize,
+		predict_type: PredictType,
+	) -> Result<&[f64]> {
+		let booster = self.pool.booster;
+		let nrow = n_rows
+			.try_into()
+			.map_err(|_| Error::conversion("number of rows doesn't fit into an i32"))?;
+		let len = match n_rows {
+			0 => 0,
+			_ => booster.predict_output_len(nrow, predict_type)?,
+		};
+		let buffer = &mut self
+			.entry
+			.as_mut()
+			.expect("entry is only taken on drop")
+			.buffer;
+		buffer.clear();
+		buffer.resize(len, 0.);
+		booster.predict_into(data, n_rows, predict_type, buffer)?;
+		Ok(buffer)
+	}
+
+	/// Predict the normal output for a single row, like [`SingleRowPredictor::predict`].
+	///
+	/// The [`SingleRowPredictor`] is created on first use, and kept in the pool with the
+	/// predictor.
+	pub fn predict_single_row(&mut self, data: &[f64]) -> Result<&[f64]> {
+		let booster = self.pool.booster;
+		let entry = self.entry.as_mut().expect("entry is only taken on drop");
+		let predictor = match &mut entry.single_row_predictor {
+			Some(predictor) => predictor,
+			predictor @ None => predictor.insert(booster.single_row_predictor()?),
+		};
+		entry.buffer.clear();
+		entry.buffer.resize(predictor.output_size(), 0.);
+		predictor.predict_into(data, &mut entry.buffer)?;
+		Ok(&entry.buffer)
+	}
+}
+
+impl Deref for PooledPredictor<'_, '_> {
+	type Target = [f64];
+
+	/// The output of the last prediction
+	fn deref(&self) -> &[f64] {
+		&self
+			.entry
+			.as_ref()
+			.expect("entry is only taken on drop")
+			.buffer
+	}
+}
+
+impl Drop for PooledPredictor<'_, '_> {
+	fn drop(&mut self) {
+		if let Some(entry) = self.entry.take() {
+			if let Ok(mut entries) = self.pool.entries.lock() {
+				entries.push(entry);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, crate::Dataset, serde_json::json};
+
+	#[test]
+	fn predict_from_threads() {
+		let dataset =
+			Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+				.unwrap();
+		let params = json! {
+			{
+				"num_iterations": 5,
+				"objective": "binary",
+				"data_random_seed": 0
+			}
+		};
+		let bst = Booster::train(dataset, &params).unwrap();
+		let rows = (0..8 * 28)
+			.map(|i| (i % 13) as f64 / 13.)
+			.collect::<Vec<_>>();
+		let expected = bst
+			.predict_with_type(&rows, 8, PredictType::Normal)
+			.unwrap();
+
+		let pool = PredictorPool::new(&bst);
+		let n_threads = 4;
+		std::thread::scope(|scope| {
+			for _ in 0..n_threads {
+				scope.spawn(|| {
+					for _ in 0..50 {
+						let mut predictor = pool.get();
+						let batch = predictor.predict(&rows, 8, PredictType::Normal).unwrap();
+						assert_eq!(batch, expected.as_slice());
+						for (row, expected) in rows.chunks(28).zip(&expected) {
+							let single = predictor.predict_single_row(row).unwrap();
+							assert!((single[0] - expected).abs() < 1e-12);
+						}
+						assert_eq!(predictor.len(), 1);
+					}
+				});
+			}
+		});
+		assert!(pool.num_predictors() >= 1);
+		assert!(pool.num_predictors() <= n_threads);
+
+		let mut predictor = pool.get();
+		assert!(predictor
+			.predict(&rows[1..], 8, PredictType::Normal)
+			.is_err());
+		assert!(predictor.predict_single_row(&rows[1..]).is_err());
+	}
+}
@@ -23,22 +23,34 @@ impl Drop for SingleRowPredictor<'_> {
 
 impl SingleRowPredictor<'_> {
 	pub fn predict(&self, data: &[f64]) -> Result<Vec<f64>> {
+		let mut out_result: Vec<f64> = vec![Default::default(); self.output_size];
+		self.predict_into(data, &mut out_result)?;
+		Ok(out_result)
+	}
+
+	/// Predict the row `data` into `out_result`, which must have the length of the output.
+	pub(crate) fn predict_into(&self, data: &[f64], out_result: &mut [f64]) -> Result<()> {
 		if data.len() != self.input_size {
-			return Err(Error::new(format!(
+			return Err(Error::invalid_input(format!(
 				"Input data size {} does not match number of features {}",
 				data.len(),
 				self.input_size
 			)));
 		}
-
-		let out_result: Vec<f64> = vec![Default::default(); self.output_size];
+		if out_result.len() != self.output_size {
+			return Err(Error::invalid_input(format!(
+				"output buffer has length {} but the prediction has {} values",
+				out_result.len(),
+				self.output_size
+			)));
+		}
 
 		let mut out_length: c_longlong = 0;
 		lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMatSingleRowFast(
 			self.handle,
 			data.as_ptr() as *const c_void,
 			&mut out_length,
-			out_result.as_ptr() as *mut c_double,
+			out_result.as_mut_ptr() as *mut c_double,
 		))?;
 
 		assert!(
@@ -46,6 +58,11 @@ impl SingleRowPredictor<'_> {
 			"Unexpected written output length"
 		);
 
-		Ok(out_result)
+		Ok(())
+	}
+
+	/// Number of values predicted for a row
+	pub(crate) fn output_size(&self) -> usize {
+		self.output_size
 	}
 }
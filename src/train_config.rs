@@ -0,0 +1,346 @@
+//! Training loop driven from Rust, for features that the LightGBM C API doesn't provide.
+
+use {
+	crate::{Booster, Dataset, Error, Params, Result},
+	std::time::{Duration, Instant},
+};
+
+type CustomEval<'a> = Box<dyn FnMut(&[f64], &[f32]) -> f64 + 'a>;
+type Progress<'a> = Box<dyn FnMut(i32, &[(String, f64)]) + 'a>;
+
+/// Configuration of a training run, for what [`Booster::train_with_params`] doesn't support.
+///
+/// Example
+/// ```
+/// use lightgbm::{Dataset, Objective, Params, TrainConfig};
+///
+/// let dataset =
+/// 	Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+/// 		.unwrap();
+/// let (train, valid) = dataset.train_valid_split(0.2, 0).unwrap();
+/// let params = Params::new()
+/// 	.objective(Objective::Binary)
+/// 	.set("num_iterations", 100);
+/// let accuracy = |preds: &[f64], labels: &[f32]| {
+/// 	let correct = preds
+/// 		.iter()
+/// 		.zip(labels)
+/// 		.filter(|&(&pred, &label)| (pred > 0.5) == (label > 0.5))
+/// 		.count();
+/// 	correct as f64 / labels.len() as f64
+/// };
+/// let booster = TrainConfig::new(params)
+/// 	.valid_data(valid)
+/// 	.custom_eval(true, accuracy)
+/// 	.early_stopping_rounds(5)
+/// 	.run(train)
+/// 	.unwrap();
+/// ```
+pub struct TrainConfig<'a> {
+	params: Params,
+	valid_data: Vec<Dataset>,
+	custom_eval: Option<(CustomEval<'a>, bool)>,
+	early_stopping_rounds: Option<usize>,
+	progress: Option<Progress<'a>>,
+	max_train_time: Option<Duration>,
+}
+
+impl<'a> TrainConfig<'a> {
+	/// Train with `params`, for `num_iterations` iterations unless training stops early.
+	pub fn new(params: Params) -> Self {
+		Self {
+			params,
+			valid_data: Vec::new(),
+			custom_eval: None,
+			early_stopping_rounds: None,
+			progress: None,
+			max_train_time: None,
+		}
+	}
+
+	/// Add a validation dataset, see [`Booster::add_valid_data`].
+	pub fn valid_data(mut self, dataset: Dataset) -> Self {
+		self.valid_data.push(dataset);
+		self
+	}
+
+	/// Evaluate the model after each iteration with `eval`, which is given the current
+	/// predictions (as returned by [`Booster::get_predict`]) and the labels of the first
+	/// validation dataset, or of the training dataset if there is no validation dataset.
+	///
+	/// `higher_is_better` tells whether a higher value of the metric means a better model,
+	/// e.g. `true` for an accuracy, `false` for an error rate. The C API can't take a metric
+	/// callback, so this runs on the Rust side and is only used for
+	/// [early stopping](Self::early_stopping_rounds).
+	pub fn custom_eval<F>(mut self, higher_is_better: bool, eval: F) -> Self
+	where
+		F: FnMut(&[f64], &[f32]) -> f64 + 'a,
+	{
+		self.custom_eval = Some((Box::new(eval), higher_is_better));
+		self
+	}
+
+	/// Stop training when the [custom eval](Self::custom_eval) hasn't improved for `rounds`
+	/// iterations, and roll the model back to the best iteration.
+	pub fn early_stopping_rounds(mut self, rounds: usize) -> Self {
+		self.early_stopping_rounds = Some(rounds);
+		self
+	}
+
+	/// Call `progress` after each iteration with the number of iterations trained so far and
+	/// the metrics of the validation datasets, e.g. to drive a progress bar.
+	///
+	/// Metrics are named `valid_{i} {metric}`, `i` being the index of the validation dataset in
+	/// the order they were [added](Self::valid_data). They are only evaluated when a progress
+	/// callback is set, and are empty if there is no validation dataset.
+	pub fn progress<F>(mut self, progress: F) -> Self
+	where
+		F: FnMut(i32, &[(String, f64)]) + 'a,
+	{
+		self.progress = Some(Box::new(progress));
+		self
+	}
+
+	/// Stop training once it has taken `max_train_time`, keeping the iterations completed so
+	/// far.
+	///
+	/// The elapsed time is checked between iterations, so training may exceed the budget by
+	/// up to one iteration, and always trains at least one. It includes the time to set up the
+	/// booster.
+	pub fn max_train_time(mut self, max_train_time: Duration) -> Self {
+		self.max_train_time = Some(max_train_time);
+		self
+	}
+
+	/// Train a model on `dataset`.
+	///
+	/// Parameters are [validated](Params::validate) before training starts.
+	pub fn run(self, dataset: Dataset) -> Result<Booster> {
+		Ok(self.run_with_stats(dataset)?.0)
+	}
+
+	/// Train a model on `dataset` like [`run`](Self::run), also returning statistics about the
+	/// training run.
+	pub fn run_with_stats(mut self, dataset: Dataset) -> Result<(Booster, TrainStats)> {
+		let start = Instant::now();
+		if self.early_stopping_rounds.is_some() && self.custom_eval.is_none() {
+			return Err(Error::invalid_input(
+				"early stopping requires a custom eval to be set",
+			));
+		}
+		let num_iterations = self.params.num_iterations()?;
+		let labels = match &self.custom_eval {
+			Some(_) => self.valid_data.first().unwrap_or(&dataset).get_label()?,
+			None => Vec::new(),
+		};
+		let eval_data_idx = usize::from(!self.valid_data.is_empty());
+		let num_valid_data = self.valid_data.len();
+
+		let mut booster = Booster::create(dataset, &self.params)?;
+		for valid_data in self.valid_data {
+			booster.add_valid_data(valid_data)?;
+		}
+
+		let mut best: Option<(f64, i64)> = None;
+		let mut iterations = 0;
+		let loop_start = Instant::now();
+		for iteration in 0..num_iterations {
+			if booster.update_one_iter()? {
+				break;
+			}
+			iterations += 1;
+			if let Some(progress) = &mut self.progress {
+				let mut evals = Vec::new();
+				for valid_idx in 0..num_valid_data {
+					evals.extend(
+						booster
+							.eval(valid_idx + 1)?
+							.into_iter()
+							.map(|(name, value)| (format!("valid_{valid_idx} {name}"), value)),
+					);
+				}
+				progress(booster.current_iteration()?, &evals);
+			}
+			if let Some((eval, higher_is_better)) = &mut self.custom_eval {
+				let score = eval(&booster.get_predict(eval_data_idx)?, &labels);
+				let improved = match best {
+					None => true,
+					Some((best_score, _)) if *higher_is_better => score > best_score,
+					Some((best_score, _)) => score < best_score,
+				};
+				if improved {
+					best = Some((score, iteration));
+				} else if let (Some(rounds), Some((_, best_iteration))) =
+					(self.early_stopping_rounds, best)
+				{
+					if iteration - best_iteration >= rounds as i64 {
+						break;
+					}
+				}
+			}
+			if self
+				.max_train_time
+				.is_some_and(|max_train_time| start.elapsed() >= max_train_time)
+			{
+				break;
+			}
+		}
+		let wall_time = loop_start.elapsed();
+
+		if let (Some(_), Some((_, best_iteration))) = (self.early_stopping_rounds, best) {
+			while i64::from(booster.current_iteration()?) > best_iteration + 1 {
+				booster.rollback_one_iter()?;
+			}
+		}
+
+		let final_eval = match num_valid_data {
+			0 => None,
+			_ => booster.eval(1)?.first().map(|&(_, value)| value),
+		};
+		let stats = TrainStats {
+			wall_time,
+			iterations,
+			final_eval,
+		};
+		Ok((booster, stats))
+	}
+}
+
+/// Statistics about a training run, see [`TrainConfig::run_with_stats`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrainStats {
+	/// Time spent in the training loop, excluding the setup of the booster
+	pub wall_time: Duration,
+	/// Number of iterations trained. With [early stopping](TrainConfig::early_stopping_rounds),
+	/// the model is then rolled back to its best iteration, so it may have fewer iterations.
+	pub iterations: i32,
+	/// Value of the first metric on the first validation dataset for the final model, if there
+	/// is a validation dataset
+	pub final_eval: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, crate::Objective};
+
+	fn accuracy(preds: &[f64], labels: &[f32]) -> f64 {
+		let correct = preds
+			.iter()
+			.zip(labels)
+			.filter(|&(&pred, &label)| (pred > 0.5) == (label > 0.5))
+			.count();
+		correct as f64 / labels.len() as f64
+	}
+
+	#[test]
+	fn custom_eval_early_stopping() {
+		let dataset =
+			Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+				.unwrap();
+		let (train, valid) = dataset.train_valid_split(0.2, 0).unwrap();
+		let params = Params::new()
+			.objective(Objective::Binary)
+			.set("num_iterations", 500);
+		let mut scores = Vec::new();
+		let booster = TrainConfig::new(params)
+			.valid_data(valid)
+			.custom_eval(true, |preds, labels| {
+				let score = accuracy(preds, labels);
+				scores.push(score);
+				score
+			})
+			.early_stopping_rounds(3)
+			.run(train)
+			.unwrap();
+
+		let best_iteration = booster.current_iteration().unwrap() as usize;
+		assert!(scores.len() < 500);
+		assert_eq!(scores.len(), best_iteration + 3);
+		let best_score = scores[best_iteration - 1];
+		assert!(scores[best_iteration..]
+			.iter()
+			.all(|&score| score <= best_score));
+	}
+
+	#[test]
+	fn run_with_stats() {
+		let dataset =
+			Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+				.unwrap();
+		let (train, valid) = dataset.train_valid_split(0.2, 0).unwrap();
+		let params = Params::new()
+			.objective(Objective::Binary)
+			.set("metric", "auc")
+			.set("num_iterations", 5);
+		let (booster, stats) = TrainConfig::new(params)
+			.valid_data(valid)
+			.run_with_stats(train)
+			.unwrap();
+
+		assert!(stats.wall_time > Duration::ZERO);
+		assert_eq!(stats.iterations, 5);
+		assert_eq!(booster.current_iteration().unwrap(), stats.iterations);
+		assert_eq!(stats.final_eval, Some(booster.eval_valid(1).unwrap()[0].1));
+	}
+
+	#[test]
+	fn progress() {
+		let dataset =
+			Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+				.unwrap();
+		let (train, valid) = dataset.train_valid_split(0.2, 0).unwrap();
+		let params = Params::new()
+			.objective(Objective::Binary)
+			.set("metric", "auc,binary_logloss")
+			.set("num_iterations", 7);
+		let mut calls = Vec::new();
+		let booster = TrainConfig::new(params)
+			.valid_data(valid)
+			.progress(|iteration, evals| calls.push((iteration, evals.to_vec())))
+			.run(train)
+			.unwrap();
+
+		assert_eq!(calls.len(), 7);
+		assert_eq!(booster.current_iteration(), Ok(7));
+		for (i, (iteration, evals)) in calls.iter().enumerate() {
+			assert_eq!(*iteration, i as i32 + 1);
+			let names: Vec<&str> = evals.iter().map(|(name, _)| name.as_str()).collect();
+			assert_eq!(names, ["valid_0 auc", "valid_0 binary_logloss"]);
+		}
+		assert_eq!(
+			calls[6].1,
+			booster
+				.eval(1)
+				.unwrap()
+				.into_iter()
+				.map(|(name, value)| (format!("valid_0 {name}"), value))
+				.collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn max_train_time() {
+		let dataset =
+			Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+				.unwrap();
+		let params = Params::new()
+			.objective(Objective::Binary)
+			.set("num_iterations", 100);
+		let booster = TrainConfig::new(params)
+			.max_train_time(Duration::from_nanos(1))
+			.run(dataset)
+			.unwrap();
+		assert_eq!(booster.current_iteration(), Ok(1));
+	}
+
+	#[test]
+	fn early_stopping_without_custom_eval() {
+		let dataset =
+			Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+				.unwrap();
+		let result = TrainConfig::new(Params::new().objective(Objective::Binary))
+			.early_stopping_rounds(3)
+			.run(dataset);
+		assert!(result.is_err());
+	}
+}